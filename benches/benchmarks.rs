@@ -1,7 +1,11 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use best_path::prelude::*;
-use best_path::prelude::floyd_warshall::calculator::{FloydWarshallCalculator, SCALE};
+use best_path::prelude::floyd_warshall::calculator::{BlockedFloydWarshallCalculator, FloydWarshallCalculator, SCALE};
 use best_path::prelude::bellman_ford::calculator::BellmanFordCalculator;
+use best_path::prelude::bellman_ford::prepared::PreparedGraph;
+use best_path::prelude::johnson::calculator::JohnsonCalculator;
+use best_path::prelude::dijkstra::calculator::DijkstraCalculator;
+use best_path::prelude::spfa::calculator::SpfaCalculator;
 
 /// Ring topology: C0->C1->C2->...->C(n-1)->C0.
 /// Rates are all < 1.0 so -log2(rate) > 0 on every edge; no cycle can accumulate a
@@ -126,5 +130,113 @@ fn bench_fw_vs_bf(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_size_scaling, bench_provider_scaling, bench_edge_density, bench_bf_size_scaling, bench_fw_vs_bf);
+/// Floyd-Warshall vs Johnson (both all-pairs) on sparse ring graphs.
+/// Johnson is O(VE log V) vs FW's O(V³), so should pull ahead as the ring grows.
+fn bench_fw_vs_johnson(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fw_vs_johnson");
+    group.sample_size(10);
+    for nodes in [10usize, 50, 100, 200] {
+        let graph = make_sparse_graph(nodes, 1);
+        group.bench_with_input(BenchmarkId::new("fw_sparse", nodes), &graph, |b, g| {
+            b.iter(|| FloydWarshallCalculator::calc_best_paths(g.as_slice()).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("johnson_sparse", nodes), &graph, |b, g| {
+            b.iter(|| JohnsonCalculator::calc_best_paths(g.as_slice()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// Bellman-Ford vs Dijkstra (both single-source) on dense graphs, where all rates are < 1.
+/// Dijkstra is O(E log V) vs BF's O(VE).
+fn bench_bf_vs_dijkstra(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bf_vs_dijkstra");
+    group.sample_size(10);
+    for nodes in [10usize, 30, 50] {
+        let graph = make_dense_graph(nodes, 1);
+        let source = format!("C{:03}", 0);
+        group.bench_with_input(BenchmarkId::new("bf_dense", nodes), &graph, |b, g| {
+            b.iter(|| BellmanFordCalculator::calc_best_paths_from(&source, g.as_slice()).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("dijkstra_dense", nodes), &graph, |b, g| {
+            b.iter(|| DijkstraCalculator::calc_best_paths_from(&source, g.as_slice()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// Sequential vs parallel Floyd-Warshall on dense graphs, sized for row updates to be spread across threads.
+/// Run with `--features parallel`, otherwise both are sequential.
+fn bench_fw_threads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fw_threads");
+    group.sample_size(10);
+    for nodes in [50usize, 100, 200, 300] {
+        let graph = make_dense_graph(nodes, 1);
+        group.bench_with_input(BenchmarkId::new("sequential_dense", nodes), &graph, |b, g| {
+            b.iter(|| FloydWarshallCalculator::calc_best_paths_sequential_with(&CalculatorOptions::default(), g.as_slice()).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("fw_dense", nodes), &graph, |b, g| {
+            b.iter(|| FloydWarshallCalculator::calc_best_paths(g.as_slice()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// Floyd-Warshall vs its cache-blocked variant on dense graphs.
+/// At these sizes the V² tables no longer fit in cache, which the blocks of rows of the blocked variant do.
+fn bench_fw_vs_blocked(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fw_vs_blocked");
+    group.sample_size(10);
+    for nodes in [500usize, 1000] {
+        let graph = make_dense_graph(nodes, 1);
+        group.bench_with_input(BenchmarkId::new("fw_dense", nodes), &graph, |b, g| {
+            b.iter(|| FloydWarshallCalculator::calc_best_paths(g.as_slice()).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("blocked_dense", nodes), &graph, |b, g| {
+            b.iter(|| BlockedFloydWarshallCalculator::calc_best_paths(g.as_slice()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// Bellman-Ford from every source, via the calculator vs a graph prepared once.
+fn bench_bf_prepared(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bf_prepared");
+    group.sample_size(10);
+    for nodes in [10usize, 30, 50] {
+        let graph = make_dense_graph(nodes, 3);
+        let sources: Vec<String> = (0..nodes).map(|i| format!("C{i:03}")).collect();
+        group.bench_with_input(BenchmarkId::new("calculator", nodes), &graph, |b, g| {
+            b.iter(|| sources.iter().map(|source| BellmanFordCalculator::calc_best_paths_from(source, g.as_slice()).unwrap()).collect::<Vec<_>>());
+        });
+        group.bench_with_input(BenchmarkId::new("prepared", nodes), &graph, |b, g| {
+            b.iter(|| {
+                let prepared = PreparedGraph::new(g.as_slice()).unwrap();
+                sources.iter().map(|source| prepared.best_paths_from(source).unwrap()).collect::<Vec<_>>()
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Bellman-Ford vs SPFA (both single-source) on sparse and dense graphs.
+/// SPFA only relaxes edges out of vertices improved in the previous round, rather than every edge in every pass.
+fn bench_bf_vs_spfa(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bf_vs_spfa");
+    group.sample_size(10);
+    for nodes in [10usize, 50, 200] {
+        let source = format!("C{:03}", 0);
+        for (topology, graph) in [("sparse", make_sparse_graph(nodes, 1)), ("dense", make_dense_graph(nodes, 1))] {
+            group.bench_with_input(BenchmarkId::new(format!("bf_{topology}"), nodes), &graph, |b, g| {
+                b.iter(|| BellmanFordCalculator::calc_best_paths_from(&source, g.as_slice()).unwrap());
+            });
+            group.bench_with_input(BenchmarkId::new(format!("spfa_{topology}"), nodes), &graph, |b, g| {
+                b.iter(|| SpfaCalculator::calc_best_paths_from(&source, g.as_slice()).unwrap());
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_size_scaling, bench_provider_scaling, bench_edge_density, bench_bf_size_scaling, bench_fw_vs_bf, bench_fw_vs_johnson, bench_bf_vs_dijkstra, bench_fw_threads, bench_fw_vs_blocked, bench_bf_prepared, bench_bf_vs_spfa);
 criterion_main!(benches);
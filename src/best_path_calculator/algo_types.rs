@@ -1,8 +1,12 @@
 #[cfg(not(feature = "std"))]
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 use core::cmp::Ordering;
+use crate::types::{Accumulation, Objective};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct Pair {
@@ -43,17 +47,17 @@ pub(crate) struct Path {
     pub(crate) edges: Vec<Edge>,
 }
 
-#[derive(Debug)]
-pub(crate) enum PathCalculationError {
-    NegativeCyclesError,
+/// Path with the smallest capacity along it, for widest paths. Edge costs are rates.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct WidePath {
+    pub(crate) capacity: u128,
+    pub(crate) edges: Vec<Edge>,
 }
 
-impl From<PathCalculationError> for crate::types::CalculatorError {
-    fn from(err: PathCalculationError) -> Self {
-        match err {
-            PathCalculationError::NegativeCyclesError => crate::types::CalculatorError::NegativeCyclesError,
-        }
-    }
+#[derive(Debug)]
+pub(crate) enum PathCalculationError {
+    /// Carries a single negative cycle, as a closed path starting and ending at the same vertex.
+    NegativeCyclesError(Path),
 }
 
 /// Returns one edge per (source, target) pair, keeping whichever edge wins under `ordering`.
@@ -71,3 +75,147 @@ pub(crate) fn best_edge_per_pair(edges: &[Edge], ordering: Ordering) -> Vec<Edge
     }
     by_pair.values().cloned().collect()
 }
+
+/// Returns one edge per (source, target) pair along with its capacity, keeping the widest, then the one with the best rate.
+/// Edges without capacity are dropped, as no amount can pass through them.
+pub(crate) fn widest_edge_per_pair(edges: &[Edge], capacities: &[u128]) -> Vec<(Edge, u128)> {
+    let mut by_pair: BTreeMap<Pair, (Edge, u128)> = BTreeMap::new();
+    for (&e, &capacity) in edges.iter().zip(capacities).filter(|(_, &capacity)| capacity > 0) {
+        by_pair.entry(e.pair)
+            .and_modify(|old| if (capacity, e.cost) > (old.1, old.0.cost) { *old = (e, capacity) })
+            .or_insert((e, capacity));
+    }
+    by_pair.into_values().collect()
+}
+
+/// Edges reduced to the best cost per pair and transformed so that the best path is always the shortest one, allowing for
+/// shortest path algorithms regardless of accumulation and objective. Original costs are retained to map paths back.
+///
+/// Products use the log trick, as per: https://www.coursera.org/lecture/algorithms-on-graphs/currency-exchange-reduction-to-shortest-paths-cw8Tm
+/// given x*y = 2^(log2(x) + log2(y)), maximising x*y is equivalent to minimising -log2(x) - log2(y).
+/// Maximising is done by negating the (log) cost. A cycle that keeps improving, eg. arbitrage (product > 1) when maximising
+/// a product, or a product < 1 when minimising, hence becomes a negative cycle.
+///
+/// Zero rates are dropped when multiplying, as log2(0) = -∞ would otherwise make any path through them "free" to minimise.
+pub(crate) struct TransformedGraph {
+    pub(crate) edges: Vec<Edge>,
+    originals: BTreeMap<(Pair, usize), f64>,
+    accumulation: Accumulation,
+}
+
+impl TransformedGraph {
+    pub(crate) fn new(edges: &[Edge], accumulation: Accumulation, objective: Objective) -> Self {
+        let quoted = edges.iter().filter(|e| accumulation == Accumulation::Sum || e.cost > 0.0).cloned().collect::<Vec<_>>();
+        let deduped = match objective {
+            Objective::Minimise => best_edge_per_pair(&quoted, Ordering::Greater),
+            Objective::Maximise => best_edge_per_pair(&quoted, Ordering::Less),
+        };
+        Self::transform(deduped, accumulation, objective)
+    }
+
+    /// As per `new`, but keeping the best edge per pair **and provider**, for algorithms that tell providers apart.
+    pub(crate) fn per_provider(edges: &[Edge], accumulation: Accumulation, objective: Objective) -> Self {
+        let mut by_provider: BTreeMap<(Pair, usize), Edge> = BTreeMap::new();
+        for &e in edges.iter().filter(|e| accumulation == Accumulation::Sum || e.cost > 0.0) {
+            by_provider.entry((e.pair, e.provider))
+                .and_modify(|old| if (objective == Objective::Minimise) == (e.cost < old.cost) { *old = e })
+                .or_insert(e);
+        }
+        Self::transform(by_provider.into_values().collect(), accumulation, objective)
+    }
+
+    fn transform(deduped: Vec<Edge>, accumulation: Accumulation, objective: Objective) -> Self {
+        let originals = deduped.iter().map(|e| ((e.pair, e.provider), e.cost)).collect();
+        let edges = deduped.iter().map(|e| Edge { cost: transform_cost(e.cost, accumulation, objective), ..*e }).collect();
+        Self { edges, originals, accumulation }
+    }
+
+    /// Original cost of a transformed edge.
+    pub(crate) fn original(&self, e: &Edge) -> f64 {
+        self.originals[&(e.pair, e.provider)]
+    }
+
+    /// Maps a transformed path back to original costs, with `total_cost` being their sum or product.
+    pub(crate) fn restore(&self, path: Path) -> Path {
+        let edges = path.edges.iter().map(|e| Edge { cost: self.original(e), ..*e }).collect::<Vec<_>>();
+        Path { total_cost: accumulate(&edges, self.accumulation), edges }
+    }
+
+    pub(crate) fn restore_error(&self, PathCalculationError::NegativeCyclesError(cycle): PathCalculationError) -> PathCalculationError {
+        PathCalculationError::NegativeCyclesError(self.restore(cycle))
+    }
+
+    /// Runs a shortest path `core` on the transformed edges, mapping resulting paths, or the negative cycle, back to original costs.
+    pub(crate) fn run<K: Ord>(
+        &self,
+        core: impl FnOnce(&[Edge]) -> Result<BTreeMap<K, Path>, PathCalculationError>,
+    ) -> Result<BTreeMap<K, Path>, PathCalculationError> {
+        let res = core(&self.edges).map_err(|err| self.restore_error(err))?;
+        Ok(res.into_iter().map(|(k, path)| (k, self.restore(path))).collect())
+    }
+}
+
+/// Transforms a cost so that the best path is the shortest one, as per `TransformedGraph`.
+pub(crate) fn transform_cost(cost: f64, accumulation: Accumulation, objective: Objective) -> f64 {
+    let cost = match accumulation {
+        Accumulation::Sum => cost,
+        Accumulation::Product => cost.log2(),
+    };
+    if objective == Objective::Maximise { -cost } else { cost }
+}
+
+/// Total of original edge costs, their sum or product.
+pub(crate) fn accumulate(edges: &[Edge], accumulation: Accumulation) -> f64 {
+    match accumulation {
+        Accumulation::Sum => edges.iter().map(|e| e.cost).sum(),
+        Accumulation::Product => edges.iter().fold(1.0, |acc, e| acc * e.cost),
+    }
+}
+
+/// Orders paths cheapest first, then fewest edges, then by edges, for deterministic ties.
+pub(crate) fn rank(a: &Path, b: &Path) -> Ordering {
+    a.total_cost.partial_cmp(&b.total_cost).unwrap()
+        .then_with(|| a.edges.len().cmp(&b.edges.len()))
+        .then_with(|| a.edges.cmp(&b.edges))
+}
+
+/// Number of vertices, given vertex IDs are dense 0..n.
+pub(crate) fn vertex_count(edges: &[Edge]) -> usize {
+    edges.iter().flat_map(|e| [e.pair.source, e.pair.target]).max().map(|m| m + 1).unwrap_or(0)
+}
+
+/// Outgoing edges per vertex, in input order.
+pub(crate) fn adjacency_list(edges: &[Edge], n: usize) -> Vec<Vec<Edge>> {
+    let mut out = vec![vec![]; n];
+    for &e in edges {
+        out[e.pair.source].push(e);
+    }
+    out
+}
+
+/// Rebuilds paths from a single source by following the predecessor chain backwards, then reversing.
+/// Vertices with infinite `dist` are unreachable and left out, the source maps to an empty path of zero cost.
+pub(crate) fn predecessor_paths(source: usize, dist: &[f64], prev_edge: &[Option<Edge>]) -> BTreeMap<usize, Path> {
+    let mut result: BTreeMap<usize, Path> = BTreeMap::new();
+    result.insert(source, Path { total_cost: 0.0, edges: vec![] });
+
+    for (target, &d) in dist.iter().enumerate() {
+        if target == source || d.is_infinite() { continue; }
+
+        let mut path_edges = vec![];
+        let mut cur = target;
+        while cur != source {
+            match prev_edge[cur] {
+                Some(e) => {
+                    path_edges.push(e);
+                    cur = e.pair.source;
+                }
+                None => break,
+            }
+        }
+        path_edges.reverse();
+
+        result.insert(target, Path { total_cost: d, edges: path_edges });
+    }
+    result
+}
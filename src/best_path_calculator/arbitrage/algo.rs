@@ -0,0 +1,66 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+pub(crate) use super::super::algo_types::{Edge, Path, TransformedGraph, adjacency_list, vertex_count};
+use super::super::floyd_warshall::algo::longest_paths_by_product;
+use crate::types::{Accumulation, Objective};
+
+/// Enumerates every elementary cycle with negative total cost.
+///
+/// Each cycle is found exactly once, rooted at its lowest vertex: a depth-first search from every vertex `s` extends
+/// the current path only through vertices above `s`, and records a cycle whenever an edge leads back to `s`.
+/// Unlike Floyd-Warshall or Bellman-Ford this does not stop at the first negative cycle, at the price of being
+/// exponential in the worst case, as is the number of elementary cycles in a dense graph.
+pub(crate) fn negative_cycles(edges: &[Edge]) -> Vec<Path> {
+    let n = vertex_count(edges);
+    let adjacency = adjacency_list(edges, n);
+
+    let mut cycles = vec![];
+    let mut on_path = vec![false; n];
+    for s in 0..n {
+        // path[d] is the edge into stack[d + 1]; stack holds (vertex, index of the next outgoing edge to explore)
+        let mut path: Vec<Edge> = vec![];
+        let mut stack: Vec<(usize, usize)> = vec![(s, 0)];
+        on_path[s] = true;
+        while let Some(&(v, i)) = stack.last() {
+            match adjacency[v].get(i) {
+                Some(&e) => {
+                    stack.last_mut().unwrap().1 += 1;
+                    let w = e.pair.target;
+                    if w == s {
+                        let total_cost = path.iter().map(|x| x.cost).sum::<f64>() + e.cost;
+                        if total_cost < 0.0 {
+                            let mut cycle = path.clone();
+                            cycle.push(e);
+                            cycles.push(Path { total_cost, edges: cycle });
+                        }
+                    } else if w > s && !on_path[w] {
+                        on_path[w] = true;
+                        path.push(e);
+                        stack.push((w, 0));
+                    }
+                }
+                None => {
+                    on_path[v] = false;
+                    stack.pop();
+                    path.pop();
+                }
+            }
+        }
+    }
+    cycles
+}
+
+/// Lists every profitable cycle (product of rates > 1) in original scale, most profitable first.
+///
+/// `longest_paths_by_product` fails on the first profitable cycle in O(n³); the cycles are only enumerated, exponential
+/// in the worst case, once it has found one.
+pub(crate) fn arbitrage_cycles(edges: &[Edge]) -> Vec<Path> {
+    if longest_paths_by_product(edges).is_ok() {
+        return vec![];
+    }
+    let log_graph = TransformedGraph::new(edges, Accumulation::Product, Objective::Maximise);
+    let mut cycles = negative_cycles(&log_graph.edges).into_iter().map(|cycle| log_graph.restore(cycle)).collect::<Vec<_>>();
+    // stable sort, ties keep the discovery order
+    cycles.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap());
+    cycles
+}
@@ -0,0 +1,59 @@
+use super::algo::*;
+use super::super::algo_types::Pair;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Five elementary cycles, three of them negative:
+///
+///   0 → 1 → 0      -1 + 0.5          = -0.5
+///   0 → 2 → 0      -1 + 2            =  1
+///   0 → 1 → 2 → 0  -1 + -1 + 2       =  0
+///   0 → 2 → 1 → 0  -1 + -1 + 0.5     = -1.5
+///   1 → 2 → 1      -1 + -1           = -2
+#[test]
+fn test_negative_cycles() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: -1.0 },
+        Edge { pair: Pair { source: 1, target: 0 }, provider: 0, cost:  0.5 },
+        Edge { pair: Pair { source: 0, target: 2 }, provider: 0, cost: -1.0 },
+        Edge { pair: Pair { source: 2, target: 0 }, provider: 0, cost:  2.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost: -1.0 },
+        Edge { pair: Pair { source: 2, target: 1 }, provider: 0, cost: -1.0 },
+    ];
+    let res = negative_cycles(&edges);
+    assert_eq!(
+        vec![
+            Path { total_cost: -0.5, edges: vec![edges[0], edges[1]] },
+            Path { total_cost: -1.5, edges: vec![edges[2], edges[5], edges[1]] },
+            Path { total_cost: -2.0, edges: vec![edges[4], edges[5]] },
+        ],
+        res
+    );
+}
+
+#[test]
+fn test_no_cycles_in_dag() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: -1.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost: -1.0 },
+        Edge { pair: Pair { source: 0, target: 2 }, provider: 0, cost: -1.0 },
+    ];
+    assert!(negative_cycles(&edges).is_empty());
+}
+
+/// Ranked by profit, in original rates: 2 * 0.75 = 1.5 beats 1.25 * 1.0 * 1.0 = 1.25; 0 → 2 → 0 at 0.5 is not arbitrage.
+#[test]
+fn test_arbitrage_cycles_ranked() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 1.25 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 2, target: 0 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 0, target: 2 }, provider: 0, cost: 0.5 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost: 2.0 },
+        Edge { pair: Pair { source: 3, target: 2 }, provider: 0, cost: 0.75 },
+    ];
+    let res = arbitrage_cycles(&edges);
+    assert_eq!(vec![1.5, 1.25], res.iter().map(|c| c.total_cost).collect::<Vec<_>>());
+    assert_eq!(vec![edges[4], edges[5]], res[0].edges);
+    assert_eq!(vec![edges[0], edges[1], edges[2]], res[1].edges);
+}
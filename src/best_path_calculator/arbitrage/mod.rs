@@ -0,0 +1,7 @@
+mod algo;
+pub mod scanner;
+
+#[cfg(test)]
+mod algo_tests;
+#[cfg(test)]
+mod scanner_tests;
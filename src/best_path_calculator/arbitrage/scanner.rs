@@ -0,0 +1,36 @@
+use super::algo;
+use super::super::{IndexedGraph, first_source};
+use crate::types::*;
+use crate::*;
+
+pub use super::super::SCALE;
+
+pub struct ArbitrageScanner {}
+
+impl<C: Currency, A: Amount, P: Provider> ArbitrageDetector<C, A, P> for ArbitrageScanner {
+
+    /// Lists all arbitrage cycles, ranked by profit ratio.
+    /// Multiple providers per pair are reduced to the best rate, as with the best path calculators, so a cycle lists every
+    /// currency at most once. Enumeration is exponential in the worst case, intended for sparse quote graphs.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::arbitrage::scanner::ArbitrageScanner;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BTC", target: "USDT" }, provider: "BINANCE" }, 36_000_000_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "BTC" }, provider: "KRAKEN" },  30_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "KRAKEN" },  500_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "ETH", target: "USDT" }, provider: "BINANCE" }, 1_900_000_000_000_000_u128),
+    /// ];
+    /// let cycles = ArbitrageScanner::find_arbitrage_cycles(in_graph).unwrap();
+    /// assert_eq!(
+    ///     vec![1_080_000_000_000_u128],
+    ///     cycles.iter().map(|c| c.total_cost).collect::<Vec<_>>()
+    /// );
+    /// ```
+    fn find_arbitrage_cycles(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(&CalculatorOptions::default(), pairs_and_prices)?;
+        algo::arbitrage_cycles(&graph.edges).into_iter().map(|cycle| graph.to_price_path(first_source(&cycle.edges), cycle)).collect()
+    }
+}
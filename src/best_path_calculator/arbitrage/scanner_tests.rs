@@ -0,0 +1,42 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::ArbitrageDetector;
+use super::scanner::ArbitrageScanner;
+use crate::best_path_calculator::fixtures::quote;
+
+/// Two independent arbitrage cycles, listed most profitable first; the better of two providers is used.
+#[test]
+fn test_lists_all_cycles_by_profit() {
+    let in_graph = vec![
+        quote("BTC",  "USDT", "A", 36000.0),
+        quote("USDT", "BTC",  "A", 0.00002),    // 0.72
+        quote("USDT", "BTC",  "B", 0.00003),    // 1.08
+        quote("ETH",  "USDT", "A", 2000.0),
+        quote("USDT", "ETH",  "A", 0.0006),     // 1.2
+        quote("BTC",  "ETH",  "A", 15.0),
+    ];
+    let res = ArbitrageScanner::find_arbitrage_cycles(&in_graph).unwrap();
+    assert_eq!(2, res.len());
+
+    assert_eq!(1_200_000_000_000, res[0].total_cost);
+    assert_eq!(
+        vec![(&"ETH".to_owned(), &"USDT".to_owned(), &"A".to_owned()), (&"USDT".to_owned(), &"ETH".to_owned(), &"A".to_owned())],
+        res[0].steps.iter().map(|s| (&s.pair.source, &s.pair.target, &s.provider)).collect::<Vec<_>>()
+    );
+
+    assert_eq!(1_080_000_000_000, res[1].total_cost);
+    assert_eq!(
+        vec![(&"BTC".to_owned(), &"USDT".to_owned(), &"A".to_owned()), (&"USDT".to_owned(), &"BTC".to_owned(), &"B".to_owned())],
+        res[1].steps.iter().map(|s| (&s.pair.source, &s.pair.target, &s.provider)).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_no_arbitrage() {
+    let in_graph = vec![
+        quote("BTC",  "USDT", "A", 36000.0),
+        quote("USDT", "BTC",  "A", 0.00002),
+    ];
+    assert!(ArbitrageScanner::find_arbitrage_cycles(&in_graph).unwrap().is_empty());
+}
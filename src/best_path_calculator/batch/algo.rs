@@ -0,0 +1,54 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::{BTreeMap, BTreeSet}, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+pub(crate) use super::super::algo_types::{Edge, Pair, Path, PathCalculationError, vertex_count};
+use super::super::bellman_ford::algo::{bellman_ford, bellman_ford_hop_limited, edges_between};
+use super::super::floyd_warshall::algo::floyd_warshall_tables;
+
+/// Algorithm serving a batch of pairs.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Strategy {
+    /// Bellman-Ford from every source, O(S·V·E).
+    BellmanFord,
+    /// Floyd-Warshall for all pairs, O(V³).
+    FloydWarshall,
+}
+
+/// Picks whichever of repeated Bellman-Ford and Floyd-Warshall does less work, for `sources` over a graph of `vertices` and `edges`.
+pub(crate) fn strategy(sources: usize, vertices: usize, edges: usize) -> Strategy {
+    if sources * edges <= vertices * vertices { Strategy::BellmanFord } else { Strategy::FloydWarshall }
+}
+
+/// Best paths from each of `sources` to each of `targets`, over transformed edges, by either strategy.
+///
+/// Runs over just the edges on some walk from a source to a target, as per `edges_between`, which also decide the strategy.
+/// Hence a negative cycle is reported only if some pair can take it, whichever the strategy. With `max_hops`, Bellman-Ford
+/// runs from every source, as Floyd-Warshall cannot bound path length. Self references are included for every source
+/// that is also a target, as per Floyd-Warshall.
+pub(crate) fn best_paths_between(edges: &[Edge], sources: &[usize], targets: &[usize], max_hops: Option<usize>) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    let sources = sources.iter().copied().collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>();
+    let targets = targets.iter().copied().collect::<BTreeSet<_>>();
+    let between = edges_between(edges, &sources, &targets.iter().copied().collect::<Vec<_>>());
+    let n = vertex_count(&between);
+
+    let mut paths = BTreeMap::new();
+    match (max_hops, strategy(sources.len(), n, between.len())) {
+        (Some(max_hops), _) => for &source in &sources {
+            paths.extend(bellman_ford_hop_limited(&between, source, max_hops).into_iter().map(|(target, path)| (Pair { source, target }, path)));
+        },
+        (None, Strategy::BellmanFord) => for &source in &sources {
+            paths.extend(bellman_ford(&between, source)?.into_iter().map(|(target, path)| (Pair { source, target }, path)));
+        },
+        (None, Strategy::FloydWarshall) => paths = floyd_warshall_tables(&between, n)?.paths(),
+    }
+    paths.retain(|pair, _| pair.source != pair.target && targets.contains(&pair.target) && sources.binary_search(&pair.source).is_ok());
+
+    // self references regardless of the walks between the sets, as long as the currency is quoted at all
+    for &source in sources.iter().filter(|source| targets.contains(source)) {
+        if edges.iter().any(|e| e.pair.source == source || e.pair.target == source) {
+            paths.insert(Pair { source, target: source }, Path { total_cost: 0.0, edges: vec![] });
+        }
+    }
+    Ok(paths)
+}
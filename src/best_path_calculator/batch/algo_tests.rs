@@ -0,0 +1,73 @@
+use super::algo::*;
+use crate::best_path_calculator::floyd_warshall::algo::shortest_paths;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Two triangles bridged by 2→3, with no equal-cost alternatives.
+fn bridged_triangles() -> Vec<Edge> {
+    [(0, 1, 1.1), (1, 2, 2.3), (2, 0, 0.7), (1, 0, 3.9), (2, 3, 1.7), (3, 4, 2.9), (4, 5, 0.5), (5, 3, 4.1), (4, 3, 1.3), (3, 5, 2.2)]
+        .into_iter()
+        .map(|(source, target, cost)| Edge { pair: Pair { source, target }, provider: 0, cost })
+        .collect()
+}
+
+#[test]
+fn test_strategy() {
+    assert_eq!(Strategy::BellmanFord, strategy(1, 10, 30));
+    assert_eq!(Strategy::BellmanFord, strategy(3, 10, 30));
+    assert_eq!(Strategy::FloydWarshall, strategy(4, 10, 30));
+    assert_eq!(Strategy::FloydWarshall, strategy(10, 10, 30));
+}
+
+/// Whichever the strategy, paths must be those of Floyd-Warshall, restricted to the sets.
+#[test]
+fn test_parity_with_floyd_warshall() {
+    let edges = bridged_triangles();
+    let all = shortest_paths(&edges).unwrap();
+    for (sources, targets) in [(vec![0], vec![5]), (vec![0, 1], vec![3, 4, 5]), (vec![0, 1, 2, 3, 4, 5], vec![0, 1, 2, 3, 4, 5])] {
+        let res = best_paths_between(&edges, &sources, &targets, None).unwrap();
+        let expected = all.iter()
+            .filter(|(pair, _)| sources.contains(&pair.source) && targets.contains(&pair.target))
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), res.len());
+        for (pair, path) in expected {
+            assert_eq!(path.edges, res[pair].edges);
+            assert!((path.total_cost - res[pair].total_cost).abs() < 1e-9);
+        }
+    }
+}
+
+/// Targets not reachable from any source are absent, and self references only come with sources that are also targets.
+#[test]
+fn test_unreachable_and_self_references() {
+    let edges = bridged_triangles();
+    let res = best_paths_between(&edges, &[3, 4], &[0, 3, 5], None).unwrap();
+    assert_eq!(vec![Pair { source: 3, target: 3 }, Pair { source: 3, target: 5 }, Pair { source: 4, target: 3 }, Pair { source: 4, target: 5 }],
+               res.keys().copied().collect::<Vec<_>>());
+    assert_eq!(0.0, res[&Pair { source: 3, target: 3 }].total_cost);
+    assert!(res[&Pair { source: 3, target: 3 }].edges.is_empty());
+    assert!(best_paths_between(&edges, &[99], &[0], None).unwrap().is_empty());
+}
+
+/// A negative cycle that no source can reach, or from which no target can be reached, is not reported, whichever the
+/// strategy. One on a route is.
+#[test]
+fn test_negative_cycle() {
+    let mut edges = bridged_triangles();
+    edges.push(Edge { pair: Pair { source: 5, target: 4 }, provider: 0, cost: -10.0 });
+    for (sources, pairs) in [(vec![0], 3), (vec![0, 1, 2, 3, 4], 9)] {
+        assert_eq!(pairs, best_paths_between(&edges, &sources, &[0, 1, 2], None).unwrap().len());
+        let PathCalculationError::NegativeCyclesError(cycle) = best_paths_between(&edges, &sources, &[5], None).unwrap_err();
+        assert!(cycle.total_cost < 0.0);
+    }
+}
+
+/// With `max_hops`, no path exceeds that many steps.
+#[test]
+fn test_hop_limited() {
+    let edges = bridged_triangles();
+    let res = best_paths_between(&edges, &[0, 1, 2], &[3, 5], Some(2)).unwrap();
+    assert_eq!(vec![Pair { source: 1, target: 3 }, Pair { source: 2, target: 3 }, Pair { source: 2, target: 5 }],
+               res.keys().copied().collect::<Vec<_>>());
+    assert!(res.values().all(|path| path.edges.len() <= 2));
+}
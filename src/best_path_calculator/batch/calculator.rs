@@ -0,0 +1,48 @@
+use super::algo;
+use super::super::IndexedGraph;
+use super::super::algo_types::TransformedGraph;
+use crate::types::*;
+use crate::*;
+
+pub use super::super::SCALE;
+
+pub struct BatchCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> MultiPairBestPathCalculator<C, A, P> for BatchCalculator {
+
+    /// Calculates best paths from every currency of `sources` to every currency of `targets`, as per `options`, running
+    /// Bellman-Ford from each source for few sources, otherwise Floyd-Warshall, over just the quotes on some route between
+    /// the sets. Paths are as per either calculator, though arbitrage is reported only if some pair can take it.
+    /// Unknown currencies are skipped.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::batch::calculator::BatchCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "DOT", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 17_430_000_000_000_u128),
+    /// ];
+    /// let res = BatchCalculator::calc_best_paths_between(&["BNB", "DOT"], &["ETH"], in_graph).unwrap();
+    /// assert_eq!(
+    ///     vec![Pair { source: "BNB", target: "ETH" }, Pair { source: "DOT", target: "ETH" }],
+    ///     res.keys().cloned().collect::<Vec<_>>()
+    /// );
+    /// ```
+    fn calc_best_paths_between_with(
+        options: &CalculatorOptions<C>,
+        sources: &[C],
+        targets: &[C],
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        let sources = sources.iter().filter_map(|c| graph.currency_indexer.try_by_val(c)).collect::<Vec<_>>();
+        let targets = targets.iter().filter_map(|c| graph.currency_indexer.try_by_val(c)).collect::<Vec<_>>();
+
+        let transformed = TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
+        let res = transformed.run(|edges| algo::best_paths_between(edges, &sources, &targets, options.max_hops))
+            .map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph(res)
+    }
+}
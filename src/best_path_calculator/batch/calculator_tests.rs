@@ -0,0 +1,48 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::{AllPairsBestPathCalculator, CalculatorOptions, MultiPairBestPathCalculator};
+use super::calculator::BatchCalculator;
+use crate::best_path_calculator::floyd_warshall::calculator::FloydWarshallCalculator;
+use crate::best_path_calculator::fixtures::real_life_graph;
+
+fn currencies(names: &[&str]) -> Vec<String> {
+    names.iter().map(|&name| name.to_owned()).collect()
+}
+
+/// For few sources (repeated Bellman-Ford) and many (Floyd-Warshall), results must be those of FW restricted to the sets.
+#[test]
+fn test_parity_with_floyd_warshall() {
+    let graph = real_life_graph();
+    let all = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+    for (sources, targets) in [
+        (currencies(&["BNB"]), currencies(&["BTC", "DOT"])),
+        (currencies(&["BNB", "BTC", "DOT", "ETH", "USDT"]), currencies(&["BNB", "BTC", "DOT", "ETH", "USDT"])),
+    ] {
+        let res = BatchCalculator::calc_best_paths_between(&sources, &targets, &graph).unwrap();
+        let expected = all.iter()
+            .filter(|(pair, _)| sources.contains(&pair.source) && targets.contains(&pair.target))
+            .map(|(pair, path)| (pair.clone(), path.clone()))
+            .collect::<crate::PricePathGraph<_, _, _>>();
+        assert_eq!(expected, res);
+    }
+}
+
+/// Unknown currencies are skipped, and so are sources with no route to any target.
+#[test]
+fn test_unknown_currencies() {
+    let graph = real_life_graph();
+    let res = BatchCalculator::calc_best_paths_between(&currencies(&["XRP", "DOT"]), &currencies(&["SOL", "ETH"]), &graph).unwrap();
+    assert_eq!(vec![Pair { source: "DOT".to_owned(), target: "ETH".to_owned() }], res.keys().cloned().collect::<Vec<_>>());
+    assert!(BatchCalculator::calc_best_paths_between(&currencies(&["XRP"]), &currencies(&["ETH"]), &graph).unwrap().is_empty());
+}
+
+/// `max_hops` applies, as per the other calculators.
+#[test]
+fn test_max_hops() {
+    let graph = real_life_graph();
+    let options = CalculatorOptions { max_hops: Some(1), ..Default::default() };
+    let res = BatchCalculator::calc_best_paths_between_with(&options, &currencies(&["DOT", "BNB"]), &currencies(&["ETH"]), &graph).unwrap();
+    assert_eq!(vec![Pair { source: "BNB".to_owned(), target: "ETH".to_owned() }], res.keys().cloned().collect::<Vec<_>>());
+}
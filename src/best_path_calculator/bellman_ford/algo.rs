@@ -2,7 +2,10 @@
 use alloc::{collections::BTreeMap, vec, vec::Vec};
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
-pub(crate) use super::super::algo_types::{Edge, Pair, Path, PathCalculationError, best_edge_per_pair};
+use crate::types::{Accumulation, Objective};
+pub(crate) use super::super::algo_types::{Edge, Pair, Path, PathCalculationError, TransformedGraph, WidePath, predecessor_paths, vertex_count, widest_edge_per_pair};
+#[cfg(test)]
+pub(crate) use super::super::algo_types::best_edge_per_pair;
 
 /// Bellman-Ford single-source shortest paths.
 ///
@@ -27,12 +30,6 @@ pub(crate) fn bellman_ford(
         return Ok(BTreeMap::new());
     }
 
-    let mut vertex_exists = vec![false; n];
-    for &e in edges {
-        vertex_exists[e.pair.source] = true;
-        vertex_exists[e.pair.target] = true;
-    }
-
     // dist[v]      = best known cost source→v
     // prev_edge[v] = incoming edge on the best known path to v
     let mut dist      = vec![f64::INFINITY; n];
@@ -55,35 +52,237 @@ pub(crate) fn bellman_ford(
         if !updated { break; }
     }
 
-    // Vth pass: any edge that can still be relaxed is part of a negative cycle
+    // Vth pass: any edge that can still be relaxed leads from a negative cycle
     for &e in edges {
         if !dist[e.pair.source].is_infinite() && dist[e.pair.source] + e.cost < dist[e.pair.target] {
-            return Err(PathCalculationError::NegativeCyclesError);
+            prev_edge[e.pair.target] = Some(e);
+            return Err(PathCalculationError::NegativeCyclesError(negative_cycle(&prev_edge, e.pair.target)));
         }
     }
 
-    // Reconstruct paths by following the predecessor chain backwards, then reversing
+    // Reconstruct paths by following the predecessor chain backwards
+    Ok(predecessor_paths(source, &dist, &prev_edge))
+}
+
+/// Bellman-Ford limited to paths of at most `max_hops` edges.
+///
+/// Runs exactly `max_hops` rounds (fewer if a round changes nothing), each relaxing edges from a copy of the previous round's
+/// distances, so that after round r `dist[v]` is the best cost over at most r edges. Predecessors are kept per round, as a
+/// later round may overwrite the predecessor a shorter path relies on.
+///
+/// Costs are bounded by the hop limit, hence negative cycles are not an error: a path may pass through one if that is best
+/// within the limit. The source maps to an empty path with zero cost, as per `bellman_ford`.
+pub(crate) fn bellman_ford_hop_limited(edges: &[Edge], source: usize, max_hops: usize) -> BTreeMap<usize, Path> {
+    let n = vertex_count(edges);
+    if source >= n {
+        return BTreeMap::new();
+    }
+
+    let mut dist = vec![f64::INFINITY; n];
+    dist[source] = 0.0;
+
+    // rounds[r][v] = edge relaxed into v during round r+1, None if v kept its cost from round r
+    let mut rounds: Vec<Vec<Option<Edge>>> = vec![];
+    for _ in 0..max_hops {
+        let prev_dist = dist.clone();
+        let mut relaxed: Vec<Option<Edge>> = vec![None; n];
+        for &e in edges {
+            let d = prev_dist[e.pair.source];
+            if d.is_infinite() { continue; }
+            let new_d = d + e.cost;
+            if new_d < dist[e.pair.target] {
+                dist[e.pair.target] = new_d;
+                relaxed[e.pair.target] = Some(e);
+            }
+        }
+        if relaxed.iter().all(Option::is_none) { break; }
+        rounds.push(relaxed);
+    }
+
     let mut result: BTreeMap<usize, Path> = BTreeMap::new();
     result.insert(source, Path { total_cost: 0.0, edges: vec![] });
+    for (target, &d) in dist.iter().enumerate() {
+        if target == source || d.is_infinite() { continue; }
 
-    for target in 0..n {
-        if target == source || dist[target].is_infinite() || !vertex_exists[target] { continue; }
-
+        // walk the rounds backwards, stepping to the predecessor only in rounds that relaxed the current vertex
         let mut path_edges = vec![];
         let mut cur = target;
-        while cur != source {
-            match prev_edge[cur] {
-                Some(e) => {
-                    path_edges.push(e);
-                    cur = e.pair.source;
-                }
-                None => break,
+        for relaxed in rounds.iter().rev() {
+            if let Some(e) = relaxed[cur] {
+                path_edges.push(e);
+                cur = e.pair.source;
+            }
+        }
+        path_edges.reverse();
+
+        result.insert(target, Path { total_cost: d, edges: path_edges });
+    }
+    result
+}
+
+/// Best path source→target, as per `bellman_ford`, or `bellman_ford_hop_limited` with `max_hops`, None if there is none.
+///
+/// Runs over just the edges on some walk source→target, so that a negative cycle is reported only if it affects the pair,
+/// ie. it is reachable from the source and reaches the target. Any other cycle is left out, as are vertices that cannot
+/// improve on the pair, and relaxation stops as soon as a round changes nothing.
+pub(crate) fn bellman_ford_pair(edges: &[Edge], source: usize, target: usize, max_hops: Option<usize>) -> Result<Option<Path>, PathCalculationError> {
+    let between = edges_between(edges, &[source], &[target]);
+    if between.is_empty() {
+        let exists = edges.iter().any(|e| e.pair.source == source || e.pair.target == source);
+        return Ok((source == target && exists).then(|| Path { total_cost: 0.0, edges: vec![] }));
+    }
+    let mut paths = match max_hops {
+        Some(max_hops) => bellman_ford_hop_limited(&between, source, max_hops),
+        None => bellman_ford(&between, source)?,
+    };
+    Ok(paths.remove(&target))
+}
+
+/// Best paths into `target` from every vertex that reaches it, as per `bellman_ford` on the reversed graph, or
+/// `bellman_ford_hop_limited` with `max_hops`. Paths, as well as a negative cycle, are reversed back, with their steps in
+/// forward order. The target maps to an empty path with zero cost.
+pub(crate) fn bellman_ford_to(edges: &[Edge], target: usize, max_hops: Option<usize>) -> Result<BTreeMap<usize, Path>, PathCalculationError> {
+    let reversed = edges.iter().map(reverse_edge).collect::<Vec<_>>();
+    let paths = match max_hops {
+        Some(max_hops) => bellman_ford_hop_limited(&reversed, target, max_hops),
+        None => bellman_ford(&reversed, target)
+            .map_err(|PathCalculationError::NegativeCyclesError(cycle)| PathCalculationError::NegativeCyclesError(reverse_path(cycle)))?,
+    };
+    Ok(paths.into_iter().map(|(source, path)| (source, reverse_path(path))).collect())
+}
+
+fn reverse_edge(e: &Edge) -> Edge {
+    Edge { pair: Pair { source: e.pair.target, target: e.pair.source }, ..*e }
+}
+
+fn reverse_path(Path { total_cost, edges }: Path) -> Path {
+    Path { total_cost, edges: edges.iter().rev().map(reverse_edge).collect() }
+}
+
+/// Edges on some walk from any of `sources` to any of `targets`, ie. from a vertex reachable from a source to one a target
+/// is reachable from.
+pub(crate) fn edges_between(edges: &[Edge], sources: &[usize], targets: &[usize]) -> Vec<Edge> {
+    let n = vertex_count(edges);
+    let from_sources = reachable(n, sources, edges.iter().map(|e| (e.pair.source, e.pair.target)));
+    let to_targets = reachable(n, targets, edges.iter().map(|e| (e.pair.target, e.pair.source)));
+    edges.iter().filter(|e| from_sources[e.pair.source] && to_targets[e.pair.target]).copied().collect()
+}
+
+/// Vertices reachable from any of `starts` over `arcs`, starts of 0..n only.
+fn reachable(n: usize, starts: &[usize], arcs: impl Iterator<Item = (usize, usize)>) -> Vec<bool> {
+    let mut adjacency = vec![vec![]; n];
+    for (from, to) in arcs {
+        adjacency[from].push(to);
+    }
+    let mut seen = vec![false; n];
+    let mut stack = starts.iter().copied().filter(|&start| start < n).collect::<Vec<_>>();
+    for &start in &stack {
+        seen[start] = true;
+    }
+    while let Some(vertex) = stack.pop() {
+        for &next in &adjacency[vertex] {
+            if !seen[next] {
+                seen[next] = true;
+                stack.push(next);
             }
         }
+    }
+    seen
+}
+
+/// All pairs best paths of at most `max_hops` edges, as per accumulation and objective. Serves the all pairs calculators,
+/// as neither Floyd-Warshall nor Johnson's Dijkstra can bound path length.
+pub(crate) fn best_paths_hop_limited(
+    edges: &[Edge],
+    accumulation: Accumulation,
+    objective: Objective,
+    max_hops: usize,
+) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    TransformedGraph::new(edges, accumulation, objective).run(|edges| Ok(all_pairs_hop_limited(edges, max_hops)))
+}
+
+/// All pairs best paths of at most `max_hops` edges, running `bellman_ford_hop_limited` from every vertex.
+/// Self references are included for every vertex present, as per Floyd-Warshall.
+pub(crate) fn all_pairs_hop_limited(edges: &[Edge], max_hops: usize) -> BTreeMap<Pair, Path> {
+    let n = vertex_count(edges);
+    let mut present = vec![false; n];
+    for e in edges {
+        present[e.pair.source] = true;
+        present[e.pair.target] = true;
+    }
+
+    let mut result = BTreeMap::new();
+    for source in (0..n).filter(|&v| present[v]) {
+        for (target, path) in bellman_ford_hop_limited(edges, source, max_hops) {
+            result.insert(Pair { source, target }, path);
+        }
+    }
+    result
+}
+
+/// Widest paths from `source`, maximising the smallest capacity along the path, with `capacities` as per `edges`.
+///
+/// Same structure as `bellman_ford`, with max-min in place of min-sum. Capacities cannot improve around a cycle, so at most
+/// V−1 passes are needed and there is no negative cycle check. The source, of unbounded capacity, is left out.
+pub(crate) fn widest_bellman_ford(edges: &[Edge], capacities: &[u128], source: usize) -> BTreeMap<usize, WidePath> {
+    let widest = widest_edge_per_pair(edges, capacities);
+    let n = vertex_count(edges);
+    if source >= n {
+        return BTreeMap::new();
+    }
+
+    // width[v] = widest known capacity source→v (0 = unreachable), prev_edge[v] = incoming edge on that path
+    let mut width = vec![0_u128; n];
+    let mut prev_edge: Vec<Option<Edge>> = vec![None; n];
+    width[source] = u128::MAX;
+
+    for _ in 0..n.saturating_sub(1) {
+        let mut updated = false;
+        for &(e, capacity) in &widest {
+            let new_width = width[e.pair.source].min(capacity);
+            if new_width > width[e.pair.target] {
+                width[e.pair.target] = new_width;
+                prev_edge[e.pair.target] = Some(e);
+                updated = true;
+            }
+        }
+        if !updated { break; }
+    }
+
+    let mut result = BTreeMap::new();
+    for (target, &w) in width.iter().enumerate() {
+        if target == source || w == 0 { continue; }
+        let mut path_edges = vec![];
+        let mut cur = target;
+        while let Some(e) = prev_edge[cur] {
+            path_edges.push(e);
+            cur = e.pair.source;
+        }
         path_edges.reverse();
+        result.insert(target, WidePath { capacity: w, edges: path_edges });
+    }
+    result
+}
+
+/// Rebuilds the negative cycle from the predecessor table, given a vertex still relaxable after V−1 passes.
+/// Walking back V times is guaranteed to land on the cycle; from there, follow predecessors until it closes.
+fn negative_cycle(prev_edge: &[Option<Edge>], relaxed: usize) -> Path {
+    let mut on_cycle = relaxed;
+    for _ in 0..prev_edge.len() {
+        match prev_edge[on_cycle] {
+            Some(e) => on_cycle = e.pair.source,
+            None => break,
+        }
+    }
 
-        result.insert(target, Path { total_cost: dist[target], edges: path_edges });
+    let mut edges = vec![];
+    let mut cur = on_cycle;
+    while let Some(e) = prev_edge[cur] {
+        edges.push(e);
+        cur = e.pair.source;
+        if cur == on_cycle { break; }
     }
+    edges.reverse();
 
-    Ok(result)
+    Path { total_cost: edges.iter().map(|e| e.cost).sum(), edges }
 }
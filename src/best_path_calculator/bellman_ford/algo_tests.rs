@@ -1,7 +1,7 @@
 use super::algo::*;
 use core::cmp::Ordering;
 #[cfg(not(feature = "std"))]
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 /// Simple chain: 0→1→2, verify direct and multi-hop paths from source 0.
 #[test]
@@ -73,6 +73,28 @@ fn test_negative_cycle() {
     assert!(bellman_ford(&edges, 0).is_err());
 }
 
+/// Negative cycle is returned, edges in traversal order.
+#[test]
+fn test_negative_cycle_path() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost:  3.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost:  2.0 },
+        Edge { pair: Pair { source: 2, target: 0 }, provider: 0, cost: -6.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost:  1.0 }, // leads off the cycle
+    ];
+    match bellman_ford(&edges, 0) {
+        Err(PathCalculationError::NegativeCyclesError(cycle)) => {
+            assert_eq!(cycle.total_cost, -1.0);
+            assert_eq!(cycle.edges.len(), 3);
+            for (e, next) in cycle.edges.iter().zip(cycle.edges.iter().cycle().skip(1)) {
+                assert_eq!(e.pair.target, next.pair.source);
+            }
+            assert!(cycle.edges.iter().all(|e| e.pair.target != 3));
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
 /// best_edge_per_pair: Ordering::Less keeps max, Ordering::Greater keeps min.
 #[test]
 fn test_unique_edges() {
@@ -131,3 +153,110 @@ fn test_youtube_distances() {
         Edge { pair: Pair { source: 2, target: 3 }, provider: 1, cost: 1.0 },
     ]);
 }
+
+/// Hop limit picks the best path within the limit; a later round overwriting an intermediate's predecessor must not leak into
+/// paths found in earlier rounds.
+#[test]
+fn test_hop_limited() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 0, target: 2 }, provider: 0, cost: 5.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 0, target: 4 }, provider: 0, cost: 0.0 },
+        Edge { pair: Pair { source: 4, target: 5 }, provider: 0, cost: 0.0 },
+        Edge { pair: Pair { source: 5, target: 1 }, provider: 0, cost: 0.5 }, // cheaper 1, but in 3 hops
+    ];
+
+    let res = bellman_ford_hop_limited(&edges, 0, 1);
+    assert_eq!(res[&2].edges, vec![edges[2]]);
+    assert!(!res.contains_key(&3));
+
+    let res = bellman_ford_hop_limited(&edges, 0, 2);
+    assert_eq!(res[&2].total_cost, 2.0);
+    assert_eq!(res[&3].edges, vec![edges[2], edges[3]]);
+
+    let res = bellman_ford_hop_limited(&edges, 0, 3);
+    assert_eq!(res[&1].edges, vec![edges[4], edges[5], edges[6]]);
+    assert_eq!(res[&3].total_cost, 3.0);
+    assert_eq!(res[&3].edges, vec![edges[0], edges[1], edges[3]]);
+    assert!(res.values().all(|p| p.edges.len() <= 3));
+
+    // once the limit is not binding, as per unrestricted Bellman-Ford
+    assert_eq!(bellman_ford(&edges, 0).unwrap(), bellman_ford_hop_limited(&edges, 0, 10));
+    assert_eq!(vec![0], bellman_ford_hop_limited(&edges, 0, 0).into_keys().collect::<Vec<_>>());
+}
+
+/// Negative cycles are not an error, the best walk within the limit may go around one.
+#[test]
+fn test_hop_limited_negative_cycle() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost: -2.0 },
+        Edge { pair: Pair { source: 2, target: 1 }, provider: 0, cost: 1.0 },
+    ];
+    let res = bellman_ford_hop_limited(&edges, 0, 4);
+    assert_eq!(res[&2].total_cost, -2.0);
+    assert_eq!(res[&2].edges, vec![edges[0], edges[1], edges[2], edges[1]]);
+}
+
+/// Widest path 0→1→3 at min(8, 6) beats the direct 0→3 at 5 and 0→2→3 at min(9, 2).
+#[test]
+fn test_widest_bellman_ford() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 1, target: 3 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 0, target: 3 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 0, target: 2 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 3, target: 0 }, provider: 0, cost: 1.0 },
+    ];
+    let res = widest_bellman_ford(&edges, &[8, 6, 5, 9, 2, 100], 0);
+    assert_eq!(WidePath { capacity: 6, edges: vec![edges[0], edges[1]] }, res[&3]);
+    assert_eq!(WidePath { capacity: 9, edges: vec![edges[3]] }, res[&2]);
+    assert_eq!(vec![1, 2, 3], res.into_keys().collect::<Vec<_>>());
+}
+
+/// Only cycles on some walk between the pair are reported, self pairs exist for any vertex present.
+#[test]
+fn test_bellman_ford_pair() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost:  3.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost:  2.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost:  1.0 },
+        Edge { pair: Pair { source: 3, target: 2 }, provider: 0, cost: -2.0 }, // cycle 2 → 3 → 2 sum = -1
+    ];
+    assert_eq!(edges[..1].to_vec(), bellman_ford_pair(&edges, 0, 1, None).unwrap().unwrap().edges);
+    assert_eq!(Some(0.0), bellman_ford_pair(&edges, 1, 1, None).unwrap().map(|path| path.total_cost));
+    assert_eq!(None, bellman_ford_pair(&edges, 1, 0, None).unwrap());
+    assert_eq!(None, bellman_ford_pair(&edges, 0, 9, None).unwrap());
+    assert!(bellman_ford_pair(&edges, 0, 2, None).is_err());
+    assert!(bellman_ford_pair(&edges, 2, 2, None).is_err());
+    // hop limited walks may pass through the cycle instead
+    assert_eq!(Some(2.0), bellman_ford_pair(&edges, 1, 3, Some(4)).unwrap().map(|path| path.total_cost));
+}
+
+/// Paths into a target come out in forward order, as does a negative cycle reaching the target.
+#[test]
+fn test_bellman_ford_to() {
+    let mut edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 3.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 1, cost: 2.0 },
+        Edge { pair: Pair { source: 3, target: 2 }, provider: 0, cost: 9.0 },
+        Edge { pair: Pair { source: 2, target: 4 }, provider: 0, cost: 1.0 }, // leaves the target, never used
+    ];
+    let res = bellman_ford_to(&edges, 2, None).unwrap();
+    assert_eq!(vec![0, 1, 2, 3], res.keys().copied().collect::<Vec<_>>());
+    assert_eq!(vec![edges[0], edges[1]], res[&0].edges);
+    assert_eq!(5.0, res[&0].total_cost);
+    assert_eq!(vec![edges[2]], res[&3].edges);
+    assert!(res[&2].edges.is_empty());
+    assert_eq!(vec![0, 1, 2, 3], bellman_ford_to(&edges, 2, Some(2)).unwrap().keys().copied().collect::<Vec<_>>());
+    assert_eq!(vec![1, 2, 3], bellman_ford_to(&edges, 2, Some(1)).unwrap().keys().copied().collect::<Vec<_>>());
+
+    edges.push(Edge { pair: Pair { source: 2, target: 1 }, provider: 0, cost: -4.0 }); // cycle 1 → 2 → 1 sum = -2
+    let PathCalculationError::NegativeCyclesError(cycle) = bellman_ford_to(&edges, 2, None).unwrap_err();
+    assert_eq!(-2.0, cycle.total_cost);
+    assert!(cycle.edges.iter().zip(cycle.edges.iter().cycle().skip(1)).all(|(e, next)| e.pair.target == next.pair.source));
+    assert!(cycle.edges.contains(&edges[1]));
+}
@@ -5,15 +5,11 @@ use alloc::{
     vec, vec::Vec,
 };
 use super::algo;
-use super::super::PositionIndexer;
+use super::super::IndexedGraph;
 use crate::types::*;
 use crate::*;
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
-use core::cmp::Ordering;
-#[cfg(not(feature = "std"))]
-#[allow(unused_imports)]
-use num_traits::Float;
 
 pub use super::super::SCALE;
 
@@ -22,87 +18,146 @@ pub struct BellmanFordCalculator {}
 impl<C: Currency, A: Amount, P: Provider> SingleSourceBestPathCalculator<C, A, P>
     for BellmanFordCalculator
 {
-    /// Calculates best paths from a single source currency using Bellman-Ford.
+    /// Calculates best paths from a single source currency using Bellman-Ford, as per `options`.
     /// O(VE) vs Floyd-Warshall's O(V³) — efficient when only one source is needed.
     ///
     /// Returns a `PricePathGraph` keyed by `Pair { source, target }` for every reachable target,
-    /// including the self-loop `source → source` with `total_cost = SCALE` and empty steps.
-    fn calc_best_paths_from(
+    /// including the self-loop `source → source` with empty steps and `total_cost` of `options.scale` (0 when summing costs).
+    /// With `max_hops`, relaxation stops after that many rounds, so no path exceeds that many steps.
+    fn calc_best_paths_from_with(
+        options: &CalculatorOptions<C>,
         source: &C,
         pairs_and_prices: &[(ProviderPair<C, P>, A)],
-    ) -> Result<PricePathGraph<C, A, P>, CalculatorError> {
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
         if pairs_and_prices.is_empty() {
             return Ok(BTreeMap::new());
         }
 
-        let currency_indexer = PositionIndexer::new(
-            pairs_and_prices.iter().flat_map(|(ProviderPair { pair: Pair { source, target }, .. }, ..)| {
-                [source, target].into_iter()
-            }),
-        );
-        let provider_indexer = PositionIndexer::new(
-            pairs_and_prices.iter().map(|(ProviderPair { provider, .. }, ..)| provider),
-        );
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
 
-        let source_idx = match currency_indexer.try_by_val(source) {
+        let source_idx = match graph.currency_indexer.try_by_val(source) {
             Some(idx) => idx,
             None => return Ok(BTreeMap::new()),
         };
 
-        // Build edges O(E)
-        let graph = pairs_and_prices.iter().map(|(pp, cost)| {
-            Ok(algo::Edge {
-                pair:     algo::Pair {
-                    source: currency_indexer.by_val(&pp.pair.source),
-                    target: currency_indexer.by_val(&pp.pair.target),
-                },
-                provider: provider_indexer.by_val(&pp.provider),
-                cost:     TryInto::<u128>::try_into(*cost)
-                    .map_err(|_| CalculatorError::ConversionError)? as f64 / SCALE,
+        // Deduplicate and transform to shortest path costs (same technique as Floyd-Warshall)
+        let transformed = algo::TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
+
+        let res = transformed.run(|edges| match options.max_hops {
+                Some(max_hops) => Ok(algo::bellman_ford_hop_limited(edges, source_idx, max_hops)),
+                None => algo::bellman_ford(edges, source_idx),
             })
-        }).collect::<Result<Vec<algo::Edge>, CalculatorError>>()?;
+            .map_err(|err| graph.to_calculator_error(err))?;
 
-        // Deduplicate and apply log transform (same technique as Floyd-Warshall)
-        let deduped = algo::best_edge_per_pair(&graph, Ordering::Greater);
-        let weight_map: BTreeMap<(algo::Pair, usize), f64> = deduped.iter()
-            .map(|e| ((e.pair, e.provider), e.cost))
-            .collect();
-        let log_edges: Vec<algo::Edge> = deduped.iter()
-            .map(|e| algo::Edge { cost: -e.cost.log2(), ..*e })
-            .collect();
+        // total_cost accumulates the original costs (same as FW), eg. product of exchange rates rather than log-space distance
+        graph.to_price_path_graph_from(source_idx, res)
+    }
+}
 
-        let res = algo::bellman_ford(&log_edges, source_idx)?;
-
-        let source_currency = currency_indexer.by_ind(source_idx);
-        let res_map = res.into_iter()
-            .map(|(target_idx, algo::Path { edges, .. })| {
-                let pair = Pair {
-                    source: source_currency.clone(),
-                    target: currency_indexer.by_ind(target_idx),
-                };
-                let steps: Vec<PathStep<C, A, P>> = edges.into_iter().map(|algo::Edge { pair: algo::Pair { source, target }, provider, .. }| {
-                    PathStep {
-                        pair: Pair {
-                            source: currency_indexer.by_ind(source),
-                            target: currency_indexer.by_ind(target),
-                        },
-                        provider: provider_indexer.by_ind(provider),
-                        cost: ((weight_map[&(algo::Pair { source, target }, provider)] * SCALE) as u128)
-                            .try_into().ok().unwrap(),
-                    }
-                }).collect();
-                // total_cost = product of original exchange rates (same as FW), not log-space distance
-                let total_cost_f64 = steps.iter()
-                    .map(|s| TryInto::<u128>::try_into(s.cost).unwrap_or(0) as f64 / SCALE)
-                    .fold(1.0_f64, |acc, r| acc * r);
-                let path = PricePath {
-                    total_cost: ((total_cost_f64 * SCALE) as u128).try_into().ok().unwrap(),
-                    steps,
-                };
-                (pair, path)
-            })
-            .collect::<BTreeMap<_, _>>();
+impl<C: Currency, A: Amount, P: Provider> SinglePairBestPathCalculator<C, A, P> for BellmanFordCalculator {
+
+    /// Calculates the best path from `source` to `target` using Bellman-Ford, as per `options`, over just the quotes on some
+    /// route between the two. Hence a negative cycle is reported only if it affects the pair, ie. it can be reached from
+    /// `source` and `target` can be reached from it.
+    ///
+    /// This only prunes the graph: as every remaining quote may still improve the target, relaxation carries on until a
+    /// round changes nothing, not until the target is settled. Where no transformed cost is negative, prefer
+    /// `DijkstraCalculator`, which stops as soon as the pair is settled.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::bellman_ford::calculator::BellmanFordCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    ///     // arbitrage between DOT and KSM, which BNB -> ETH cannot take
+    ///     (ProviderPair { pair: Pair { source: "DOT", target: "KSM" }, provider: "COINGECKO" }, 2_000_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "KSM", target: "DOT" }, provider: "COINGECKO" }, 600_000_000_000_u128),
+    /// ];
+    /// let res = BellmanFordCalculator::calc_best_path(&"BNB", &"ETH", in_graph).unwrap().unwrap();
+    /// assert_eq!(999_701_550_000_u128, res.total_cost);
+    /// ```
+    fn calc_best_path_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<Option<PricePath<C, A, P>>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        let (Some(source_idx), Some(target_idx)) = (graph.currency_indexer.try_by_val(source), graph.currency_indexer.try_by_val(target)) else {
+            return Ok(None);
+        };
+
+        let transformed = algo::TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
+        let path = algo::bellman_ford_pair(&transformed.edges, source_idx, target_idx, options.max_hops)
+            .map_err(|err| graph.to_calculator_error(transformed.restore_error(err)))?;
+        path.map(|path| graph.to_price_path(source_idx, transformed.restore(path))).transpose()
+    }
+}
+
+impl<C: Currency, A: Amount, P: Provider> SingleTargetBestPathCalculator<C, A, P> for BellmanFordCalculator {
+
+    /// Calculates best paths into a single target currency using Bellman-Ford on the reversed graph, as per `options`.
+    ///
+    /// Returns a `PricePathGraph` keyed by `Pair { source, target }` for every source reaching the target, with steps in
+    /// forward order and `total_cost` as per `calc_best_paths_from_with`, including the self-loop `target → target`.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::bellman_ford::calculator::BellmanFordCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "ETH" }, provider: "CRYPTO_COMPARE" }, 152_700_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "ETH", target: "USDT" }, provider: "COINGECKO" }, 2_384_990_000_000_000_u128),
+    /// ];
+    /// let res = BellmanFordCalculator::calc_best_paths_to(&"USDT", in_graph).unwrap();
+    /// let bnb = &res[&Pair { source: "BNB", target: "USDT" }];
+    /// assert_eq!(vec![Pair { source: "BNB", target: "ETH" }, Pair { source: "ETH", target: "USDT" }],
+    ///            bnb.steps.iter().map(|step| step.pair.clone()).collect::<Vec<_>>());
+    /// assert_eq!(364_187_973_000_000_u128, bnb.total_cost);
+    /// ```
+    fn calc_best_paths_to_with(
+        options: &CalculatorOptions<C>,
+        target: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        if pairs_and_prices.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        let target_idx = match graph.currency_indexer.try_by_val(target) {
+            Some(idx) => idx,
+            None => return Ok(BTreeMap::new()),
+        };
+
+        let transformed = algo::TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
 
-        Ok(res_map)
+        let res = transformed.run(|edges| algo::bellman_ford_to(edges, target_idx, options.max_hops)
+                .map(|paths| paths.into_iter().map(|(source, path)| (algo::Pair { source, target: target_idx }, path)).collect()))
+            .map_err(|err| graph.to_calculator_error(err))?;
+
+        graph.to_price_path_graph(res)
+    }
+}
+
+impl<C: Currency, A: Amount, P: Provider> SingleSourceWidestPathCalculator<C, A, P> for BellmanFordCalculator {
+
+    /// Calculates widest paths from a single source currency using Bellman-Ford with max-min semantics.
+    /// Output as per `FloydWarshallCalculator::calc_widest_paths`, for pairs from `source`.
+    fn calc_widest_paths_from(source: &C, quotes: &[(ProviderPair<C, P>, A, A)]) -> Result<WidestPathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let (graph, capacities) = IndexedGraph::with_capacities(quotes)?;
+
+        let source_idx = match graph.currency_indexer.try_by_val(source) {
+            Some(idx) => idx,
+            None => return Ok(BTreeMap::new()),
+        };
+
+        let res = algo::widest_bellman_ford(&graph.edges, &capacities, source_idx).into_iter()
+            .map(|(target, path)| (algo::Pair { source: source_idx, target }, path))
+            .collect();
+        graph.to_widest_path_graph(res)
     }
 }
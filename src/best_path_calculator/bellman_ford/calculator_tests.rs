@@ -1,34 +1,13 @@
 #[cfg(not(feature = "std"))]
 #[allow(unused_imports)]
-use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use alloc::{borrow::ToOwned, collections::BTreeMap, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
 use crate::types::*;
-use crate::{AllPairsBestPathCalculator, SingleSourceBestPathCalculator};
+use crate::{AllPairsBestPathCalculator, CurrencyDecimals, AllPairsWidestPathCalculator, SinglePairBestPathCalculator, SingleSourceBestPathCalculator, SingleSourceWidestPathCalculator, SingleTargetBestPathCalculator};
 use super::calculator::{BellmanFordCalculator, SCALE};
 use crate::best_path_calculator::floyd_warshall::calculator::FloydWarshallCalculator;
-
-fn real_life_graph() -> Vec<(ProviderPair<String, String>, u128)> {
-    const P: &str = "P";
-    vec![
-        ("BTC",  "USDT", 5997.42),
-        ("USDT", "BTC",  0.00002777),
-        ("ETH",  "USDT", 2384.99),
-        ("USDT", "ETH",  0.0004192),
-        ("BNB",  "USDT", 364.19),
-        ("USDT", "BNB",  0.002745),
-        ("DOT",  "USDT", 17.43),
-        ("USDT", "DOT",  0.05737),
-        ("BTC",  "ETH",  15.09),
-        ("ETH",  "BTC",  0.06626),
-        ("ETH",  "BNB",  6.548),
-        ("BNB",  "ETH",  0.1527),
-    ].into_iter().map(|(src, tgt, rate)| (
-        ProviderPair {
-            pair: Pair { source: src.to_owned(), target: tgt.to_owned() },
-            provider: P.to_owned(),
-        },
-        (rate * SCALE) as u128,
-    )).collect()
-}
+use crate::best_path_calculator::fixtures::real_life_graph;
 
 /// BF from source X must return the same paths as FW filtered to rows where source == X.
 #[test]
@@ -95,6 +74,66 @@ fn test_multi_hop_total_cost() {
     assert_eq!(path.total_cost, product);
 }
 
+/// Amounts in 10^18, eg. as sourced from EVM chains, give the same paths, with costs and self references in 10^18.
+#[test]
+fn test_scale() {
+    let graph = real_life_graph();
+    let graph_18 = graph.iter().map(|(pp, amount)| (pp.clone(), amount * 1_000_000)).collect::<Vec<_>>();
+    let options = CalculatorOptions { scale: 1_000_000_000_000_000_000, ..Default::default() };
+    for source in ["BNB", "BTC", "DOT", "ETH", "USDT"] {
+        let expected = BellmanFordCalculator::calc_best_paths_from(&source.to_owned(), &graph).unwrap();
+        let res = BellmanFordCalculator::calc_best_paths_from_with(&options, &source.to_owned(), &graph_18).unwrap();
+        assert_eq!(expected.keys().collect::<Vec<_>>(), res.keys().collect::<Vec<_>>());
+        for (pair, path) in res {
+            assert_eq!(expected[&pair].steps.iter().map(|s| s.cost * 1_000_000).collect::<Vec<_>>(), path.steps.iter().map(|s| s.cost).collect::<Vec<_>>());
+            // exact products, rounded down in either scale
+            assert_eq!(expected[&pair].total_cost, path.total_cost / 1_000_000);
+        }
+    }
+    let self_path = &BellmanFordCalculator::calc_best_paths_from_with(&options, &"BNB".to_owned(), &graph_18).unwrap()[&Pair { source: "BNB".to_owned(), target: "BNB".to_owned() }];
+    assert_eq!(1_000_000_000_000_000_000, self_path.total_cost);
+}
+
+/// With currency decimals, BF from source X returns the FW rows where source == X, and sums leave decimals out.
+#[test]
+fn test_decimals_parity_with_floyd_warshall() {
+    let graph = real_life_graph();
+    let decimals = ["BNB", "BTC", "DOT", "ETH", "USDT"].into_iter().zip([18, 8, 10, 18, 6]).map(|(c, d)| (c.to_owned(), d)).collect::<CurrencyDecimals<_>>();
+    let sum = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() };
+    for options in [CalculatorOptions { decimals: decimals.clone(), ..Default::default() }, CalculatorOptions { decimals: decimals.clone(), ..sum.clone() }] {
+        let all = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+        for source in ["BNB", "BTC", "DOT", "ETH", "USDT"] {
+            let res = BellmanFordCalculator::calc_best_paths_from_with(&options, &source.to_owned(), &graph).unwrap();
+            let expected = all.iter().filter(|(pair, _)| pair.source == source).map(|(pair, path)| (pair.clone(), path.clone())).collect::<BTreeMap<_, _>>();
+            assert_eq!(expected, res);
+        }
+    }
+    assert_eq!(
+        BellmanFordCalculator::calc_best_paths_from_with(&sum, &"BTC".to_owned(), &graph).unwrap(),
+        BellmanFordCalculator::calc_best_paths_from_with(&CalculatorOptions { decimals, ..sum }, &"BTC".to_owned(), &graph).unwrap(),
+    );
+}
+
+/// Narrow amounts that cannot take a result fail, naming its pair, rather than panic.
+#[test]
+fn test_overflow_error() {
+    // 2^32 * 2^32 overflows u64, in any scale
+    let graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P" }, 1_u64 << 32),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P" }, 1_u64 << 32),
+    ];
+    let options = CalculatorOptions { scale: 1, ..Default::default() };
+    assert_eq!(
+        Err(CalculatorError::OverflowError(Pair { source: "A", target: "C" })),
+        BellmanFordCalculator::calc_best_paths_from_with(&options, &"A", &graph),
+    );
+    assert_eq!(
+        Err(CalculatorError::OverflowError(Pair { source: "A", target: "C" })),
+        BellmanFordCalculator::calc_best_path_with(&options, &"A", &"C", &graph),
+    );
+    assert_eq!(2, BellmanFordCalculator::calc_best_paths_from_with(&options, &"B", &graph).unwrap().len());
+}
+
 /// Unknown source returns empty map (not an error).
 #[test]
 fn test_unknown_source_returns_empty() {
@@ -102,3 +141,184 @@ fn test_unknown_source_returns_empty() {
     let res = BellmanFordCalculator::calc_best_paths_from(&"XYZ".to_owned(), &graph).unwrap();
     assert!(res.is_empty());
 }
+
+/// Arbitrage reachable from the source is reported as a cycle, with the profit ratio as total_cost.
+#[test]
+fn test_negative_cycle_reported() {
+    let mut graph = real_life_graph();
+    graph.retain(|(pp, _)| pp.pair != Pair { source: "USDT".to_owned(), target: "DOT".to_owned() });
+    graph.push((
+        ProviderPair { pair: Pair { source: "USDT".to_owned(), target: "DOT".to_owned() }, provider: "Q".to_owned() },
+        (0.06 * SCALE) as u128, // 17.43 * 0.06 = 1.0458
+    ));
+    match BellmanFordCalculator::calc_best_paths_from(&"BNB".to_owned(), &graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            assert_eq!(cycle.steps.len(), 2);
+            assert_eq!(cycle.steps[0].pair.source, cycle.steps[1].pair.target);
+            assert_eq!(cycle.steps[1].pair.source, cycle.steps[0].pair.target);
+            assert!(cycle.steps.iter().any(|s| s.provider == "Q"));
+            assert!((cycle.total_cost as f64 / SCALE - 1.0458).abs() < 1e-9);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+/// BF must agree with FW when summing costs too.
+#[test]
+fn test_sum_parity_with_floyd_warshall() {
+    let graph = real_life_graph();
+    for objective in [Objective::Minimise, Objective::Maximise] {
+        let options = CalculatorOptions { accumulation: Accumulation::Sum, objective, ..Default::default() };
+        let fw = FloydWarshallCalculator::calc_best_paths_with(&options, &graph);
+        let bf = BellmanFordCalculator::calc_best_paths_from_with(&options, &"BNB".to_owned(), &graph);
+        match (fw, bf) {
+            (Ok(fw), Ok(bf)) => {
+                let fw_from_source = fw.into_iter().filter(|(p, _)| p.source == "BNB").collect::<BTreeMap<_, _>>();
+                assert_eq!(fw_from_source, bf);
+            }
+            // every pair is quoted both ways, so maximising a sum is unbounded
+            (Err(CalculatorError::NegativeCyclesError(_)), Err(CalculatorError::NegativeCyclesError(_))) => assert_eq!(Objective::Maximise, objective),
+            other => panic!("FW and BF disagree: {other:?}"),
+        }
+    }
+}
+
+/// BF must agree with FW when minimising a product; the real life graph, read as cost factors, has round trips below 1.
+#[test]
+fn test_product_minimise_parity_with_floyd_warshall() {
+    let graph = real_life_graph().into_iter().filter(|(pp, _)| pp.pair.source < pp.pair.target).collect::<Vec<_>>();
+    let options = CalculatorOptions { accumulation: Accumulation::Product, objective: Objective::Minimise, ..Default::default() };
+    let fw = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+    for source in ["BNB", "BTC", "DOT", "ETH"] {
+        let bf = BellmanFordCalculator::calc_best_paths_from_with(&options, &source.to_owned(), &graph).unwrap();
+        let fw_from_source = fw.clone().into_iter().filter(|(p, _)| p.source == source).collect::<BTreeMap<_, _>>();
+        assert_eq!(fw_from_source, bf);
+    }
+
+    assert!(matches!(
+        BellmanFordCalculator::calc_best_paths_from_with(&options, &"BNB".to_owned(), &real_life_graph()),
+        Err(CalculatorError::NegativeCyclesError(cycle)) if cycle.total_cost < 1_000_000_000_000
+    ));
+}
+
+/// No path exceeds max_hops, paths within the limit are the best available, and a non binding limit changes nothing.
+#[test]
+fn test_max_hops() {
+    let graph = real_life_graph();
+    let source = "DOT".to_owned();
+    let unlimited = BellmanFordCalculator::calc_best_paths_from(&source, &graph).unwrap();
+    assert!(unlimited.values().any(|p| p.steps.len() > 2));
+
+    for max_hops in 0..=4 {
+        let options = CalculatorOptions { max_hops: Some(max_hops), ..Default::default() };
+        let res = BellmanFordCalculator::calc_best_paths_from_with(&options, &source, &graph).unwrap();
+        assert!(res.values().all(|p| p.steps.len() <= max_hops));
+        for (pair, path) in &res {
+            let best = &unlimited[pair];
+            if best.steps.len() <= max_hops {
+                assert_eq!(best, path);
+            } else {
+                assert!(path.total_cost <= best.total_cost);
+            }
+        }
+    }
+
+    let options = CalculatorOptions { max_hops: Some(1), ..Default::default() };
+    let res = BellmanFordCalculator::calc_best_paths_from_with(&options, &source, &graph).unwrap();
+    assert_eq!(vec!["DOT", "USDT"], res.keys().map(|p| p.target.as_str()).collect::<Vec<_>>());
+}
+
+/// Widest paths from a source must agree with Floyd-Warshall's.
+#[test]
+fn test_widest_parity_with_floyd_warshall() {
+    let quotes = real_life_graph().into_iter().enumerate()
+        .map(|(i, (pp, rate))| (pp, rate, (i as u128 * 7919) % 100))
+        .collect::<Vec<_>>();
+    let fw = FloydWarshallCalculator::calc_widest_paths(&quotes).unwrap();
+    for source in ["BNB", "BTC", "DOT", "ETH", "USDT"] {
+        let bf = BellmanFordCalculator::calc_widest_paths_from(&source.to_owned(), &quotes).unwrap();
+        let fw_from_source = fw.clone().into_iter().filter(|(p, _)| p.source == source).collect::<BTreeMap<_, _>>();
+        assert_eq!(fw_from_source.keys().collect::<Vec<_>>(), bf.keys().collect::<Vec<_>>());
+        for (pair, widest) in bf {
+            assert_eq!(fw_from_source[&pair].capacity, widest.capacity);
+            assert_eq!(widest.capacity, widest.path.steps.iter().map(|s| {
+                quotes.iter().find(|(pp, _, _)| pp.pair == s.pair).unwrap().2
+            }).min().unwrap());
+        }
+    }
+}
+
+/// A single pair query must return the same path as FW, for every pair, options and hop limit.
+#[test]
+fn test_pair_parity_with_floyd_warshall() {
+    let graph = real_life_graph();
+    for options in [
+        CalculatorOptions::default(),
+        CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+        CalculatorOptions { max_hops: Some(2), ..Default::default() },
+    ] {
+        let fw = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+        for source in ["BTC", "USDT", "ETH", "BNB", "DOT"] {
+            for target in ["BTC", "USDT", "ETH", "BNB", "DOT"] {
+                let pair = Pair { source: source.to_owned(), target: target.to_owned() };
+                assert_eq!(fw.get(&pair), BellmanFordCalculator::calc_best_path_with(&options, &pair.source, &pair.target, &graph).unwrap().as_ref());
+            }
+        }
+    }
+    assert_eq!(None, BellmanFordCalculator::calc_best_path(&"BNB".to_owned(), &"XYZ".to_owned(), &graph).unwrap());
+}
+
+/// Only arbitrage on some route between the pair is reported, unlike arbitrage the source can merely reach.
+#[test]
+fn test_pair_negative_cycle() {
+    let mut graph = real_life_graph();
+    for (source, target, rate) in [("ETH", "SOL", 150.0), ("SOL", "XRP", 2.0), ("XRP", "SOL", 0.6)] {
+        graph.push((ProviderPair { pair: Pair { source: source.to_owned(), target: target.to_owned() }, provider: "Q".to_owned() }, (rate * SCALE) as u128));
+    }
+    let (bnb, eth, xrp) = ("BNB".to_owned(), "ETH".to_owned(), "XRP".to_owned());
+    assert!(BellmanFordCalculator::calc_best_paths_from(&bnb, &graph).is_err());
+
+    let expected = FloydWarshallCalculator::calc_best_paths(&real_life_graph()).unwrap().remove(&Pair { source: bnb.clone(), target: eth.clone() });
+    assert_eq!(expected, BellmanFordCalculator::calc_best_path(&bnb, &eth, &graph).unwrap());
+    match BellmanFordCalculator::calc_best_path(&bnb, &xrp, &graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            assert_eq!(vec!["SOL", "XRP"], cycle.steps.iter().map(|s| s.pair.source.as_str()).collect::<Vec<_>>());
+            assert!((cycle.total_cost as f64 / SCALE - 1.2).abs() < 1e-9);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+/// Paths into every target must be those of FW for pairs into that target, with steps in forward order.
+#[test]
+fn test_single_target_parity_with_floyd_warshall() {
+    let graph = real_life_graph();
+    let fw = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+    for target in ["BNB", "BTC", "DOT", "ETH", "USDT"] {
+        let expected = fw.iter()
+            .filter(|(pair, _)| pair.target == target)
+            .map(|(pair, path)| (pair.clone(), path.clone()))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(expected, BellmanFordCalculator::calc_best_paths_to(&target.to_owned(), &graph).unwrap());
+    }
+    assert!(BellmanFordCalculator::calc_best_paths_to(&"XYZ".to_owned(), &graph).unwrap().is_empty());
+}
+
+/// Arbitrage reaching the target is reported in forward order, with the profit ratio as total_cost.
+#[test]
+fn test_single_target_negative_cycle() {
+    let mut graph = real_life_graph();
+    for (source, target, rate) in [("SOL", "XRP", 2.0), ("XRP", "SOL", 0.6), ("SOL", "USDT", 150.0)] {
+        graph.push((ProviderPair { pair: Pair { source: source.to_owned(), target: target.to_owned() }, provider: "Q".to_owned() }, (rate * SCALE) as u128));
+    }
+    assert!(BellmanFordCalculator::calc_best_paths_to(&"XRP".to_owned(), &real_life_graph()).unwrap().is_empty());
+    match BellmanFordCalculator::calc_best_paths_to(&"USDT".to_owned(), &graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            assert_eq!(2, cycle.steps.len());
+            assert_eq!(cycle.steps[0].pair.target, cycle.steps[1].pair.source);
+            assert_eq!(cycle.steps[1].pair.target, cycle.steps[0].pair.source);
+            assert!((cycle.total_cost as f64 / SCALE - 1.2).abs() < 1e-9);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
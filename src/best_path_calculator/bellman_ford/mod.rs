@@ -1,7 +1,10 @@
-mod algo;
+pub(crate) mod algo;
 pub mod calculator;
+pub mod prepared;
 
 #[cfg(test)]
 mod algo_tests;
 #[cfg(test)]
 mod calculator_tests;
+#[cfg(test)]
+mod prepared_tests;
@@ -0,0 +1,70 @@
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+use core::marker::PhantomData;
+use super::algo;
+use super::super::{IndexedGraph, Labels};
+use super::super::algo_types::TransformedGraph;
+use crate::types::*;
+use crate::*;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Graph indexed, deduplicated and transformed once, as per `BellmanFordCalculator`, then queried from many sources.
+///
+/// Keeps the sorted currencies and providers, and the best transformed edge per pair, so that each query only runs
+/// Bellman-Ford. Results are identical to `BellmanFordCalculator::calc_best_paths_from_with` with the same options.
+pub struct PreparedGraph<C: Currency, A: Amount, P: Provider> {
+    options: CalculatorOptions<C>,
+    labels: Labels<C, P>,
+    transformed: TransformedGraph,
+    _amount: PhantomData<A>,
+}
+
+impl<C: Currency, A: Amount, P: Provider> PreparedGraph<C, A, P> {
+    /// Prepares the graph for queries maximising the product of exchange rates.
+    pub fn new(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<Self, CalculatorError<C, A, P>> {
+        Self::with_options(&CalculatorOptions::default(), pairs_and_prices)
+    }
+
+    /// Prepares the graph for queries as per `options`.
+    pub fn with_options(options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<Self, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        Ok(Self {
+            options: options.clone(),
+            transformed: TransformedGraph::new(&graph.edges, options.accumulation, options.objective),
+            labels: graph.into_labels(),
+            _amount: PhantomData,
+        })
+    }
+
+    /// Calculates best paths from a single source currency, as per `BellmanFordCalculator::calc_best_paths_from_with`.
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::bellman_ford::prepared::PreparedGraph;
+    ///
+    /// let graph = PreparedGraph::new(&[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ]).unwrap();
+    /// for source in ["BNB", "USDT"] {
+    ///     let res = graph.best_paths_from(&source).unwrap();
+    ///     assert!(res.contains_key(&Pair { source, target: "ETH" }));
+    /// }
+    /// ```
+    pub fn best_paths_from(&self, source: &C) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let Ok(source_idx) = self.labels.currencies.binary_search(source) else {
+            return Ok(BTreeMap::new());
+        };
+
+        let res = self.transformed.run(|edges| match self.options.max_hops {
+                Some(max_hops) => Ok(algo::bellman_ford_hop_limited(edges, source_idx, max_hops)),
+                None => algo::bellman_ford(edges, source_idx),
+            })
+            .map_err(|err| self.labels.to_calculator_error(err))?;
+
+        res.into_iter().map(|(target, path)| {
+            let pair = Pair { source: source.clone(), target: self.labels.currencies[target].clone() };
+            Ok((pair, self.labels.to_price_path(source_idx, path)?))
+        }).collect()
+    }
+}
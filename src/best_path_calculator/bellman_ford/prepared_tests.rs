@@ -0,0 +1,48 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::SingleSourceBestPathCalculator;
+use super::calculator::BellmanFordCalculator;
+use super::prepared::PreparedGraph;
+use crate::best_path_calculator::fixtures::{quote, real_life_graph};
+
+/// Every source, including unknown ones, gives the very same result as the calculator, for all options.
+#[test]
+fn test_parity_with_calculator() {
+    let mut graph = real_life_graph();
+    graph.push(quote("BNB", "ETH", "Q", 0.1520));
+    for options in [
+        CalculatorOptions::default(),
+        CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+        CalculatorOptions { accumulation: Accumulation::Product, objective: Objective::Minimise, ..Default::default() },
+        CalculatorOptions { max_hops: Some(2), ..Default::default() },
+    ] {
+        let prepared = PreparedGraph::with_options(&options, &graph).unwrap();
+        for source in ["BTC", "USDT", "ETH", "BNB", "DOT", "XYZ"] {
+            let source = source.to_owned();
+            assert_eq!(
+                BellmanFordCalculator::calc_best_paths_from_with(&options, &source, &graph),
+                prepared.best_paths_from(&source),
+            );
+        }
+    }
+}
+
+/// Arbitrage is reported per query, as per the calculator.
+#[test]
+fn test_negative_cycle_reported() {
+    let mut graph = real_life_graph();
+    graph.push(quote("USDT", "DOT", "Q", 0.06));
+    let prepared = PreparedGraph::new(&graph).unwrap();
+    let source = "BNB".to_owned();
+    let res = prepared.best_paths_from(&source);
+    assert!(matches!(res, Err(CalculatorError::NegativeCyclesError(_))));
+    assert_eq!(BellmanFordCalculator::calc_best_paths_from(&source, &graph), res);
+}
+
+#[test]
+fn test_empty() {
+    let empty: &[(ProviderPair<&str, &str>, u128)] = &[];
+    assert!(PreparedGraph::new(empty).unwrap().best_paths_from(&"BNB").unwrap().is_empty());
+}
@@ -0,0 +1,254 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+use crate::types::{Accumulation, Objective};
+pub(crate) use super::super::algo_types::Pair;
+
+/// Fractional bits of fixed-point logs, ie. their resolution is 2^-48.
+pub(crate) const FRAC_BITS: u32 = 48;
+
+/// Quote with an integer cost, transformed so that the best path is the shortest one, as per `TransformedGraph`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct IntEdge {
+    pub(crate) pair: Pair,
+    /// Position of the quote in the calculator's input.
+    pub(crate) quote: usize,
+    pub(crate) cost: i128,
+}
+
+/// Edges of a path, or of a negative cycle, starting and ending in the same vertex.
+pub(crate) type Route = Vec<IntEdge>;
+
+#[derive(Debug)]
+pub(crate) struct NegativeCycle(pub(crate) Route);
+
+/// ⌊log2(x) · 2^FRAC_BITS⌋ for x > 0, by repeated squaring of the mantissa, each squaring yielding a fractional bit.
+/// Mantissas are truncated, so the result may fall short by 1, never more.
+pub(crate) fn log2_fixed(x: u128) -> i128 {
+    let int = 127 - x.leading_zeros();
+    // mantissa x / 2^int in [1, 2), as Q1.63
+    let mut mantissa = if int >= 63 { x >> (int - 63) } else { x << (63 - int) };
+    let mut log = (int as i128) << FRAC_BITS;
+    for bit in (0..FRAC_BITS).rev() {
+        // mantissa < 2^64, hence its square < 2^128
+        mantissa = (mantissa * mantissa) >> 63;
+        if mantissa >= 1 << 64 {
+            mantissa >>= 1;
+            log |= 1 << bit;
+        }
+    }
+    log
+}
+
+/// Keeps the best quote per pair, the first of equal ones, and transforms its amount into an integer cost, as per
+/// `TransformedGraph::new`, with `amounts` in the order of `pairs`. Zero amounts are dropped when multiplying.
+///
+/// Products take fixed-point logs of `amount / scale`, with costs rounded up, ie. by 2 as `log2_fixed` may fall short by 1,
+/// so that a cycle of product exactly 1 is never taken for arbitrage, at the expense of missing arbitrage below 2^-46 per
+/// quote. Sums saturate at the bounds of i128.
+pub(crate) fn transform(pairs: &[Pair], amounts: &[u128], scale: u128, accumulation: Accumulation, objective: Objective) -> Vec<IntEdge> {
+    let mut best: BTreeMap<Pair, usize> = BTreeMap::new();
+    for (quote, (&pair, &amount)) in pairs.iter().zip(amounts).enumerate() {
+        if accumulation == Accumulation::Product && amount == 0 { continue; }
+        best.entry(pair)
+            .and_modify(|old| {
+                let better = match objective {
+                    Objective::Minimise => amount < amounts[*old],
+                    Objective::Maximise => amount > amounts[*old],
+                };
+                if better { *old = quote }
+            })
+            .or_insert(quote);
+    }
+
+    let log_scale = log2_fixed(scale);
+    best.into_iter().map(|(pair, quote)| {
+        let amount = amounts[quote];
+        let cost = match (accumulation, objective) {
+            (Accumulation::Sum, Objective::Minimise) => i128::try_from(amount).unwrap_or(i128::MAX),
+            (Accumulation::Sum, Objective::Maximise) => i128::try_from(amount).map_or(i128::MIN, |amount| -amount),
+            (Accumulation::Product, Objective::Minimise) => log2_fixed(amount) + 2 - log_scale,
+            (Accumulation::Product, Objective::Maximise) => log_scale + 2 - log2_fixed(amount),
+        };
+        IntEdge { pair, quote, cost }
+    }).collect()
+}
+
+/// Number of vertices, given vertex IDs are dense 0..n.
+pub(crate) fn vertex_count(edges: &[IntEdge]) -> usize {
+    edges.iter().flat_map(|e| [e.pair.source, e.pair.target]).max().map(|m| m + 1).unwrap_or(0)
+}
+
+/// Relaxes `e` into `dist` and `prev_edge`, if it improves on its target.
+fn relax(e: IntEdge, dist: &mut [Option<i128>], prev_edge: &mut [Option<IntEdge>]) -> bool {
+    let Some(d) = dist[e.pair.source] else { return false };
+    let new_d = d.saturating_add(e.cost);
+    if dist[e.pair.target].map_or(true, |old| new_d < old) {
+        dist[e.pair.target] = Some(new_d);
+        prev_edge[e.pair.target] = Some(e);
+        true
+    } else {
+        false
+    }
+}
+
+/// Bellman-Ford passes from the vertices of known `dist`, as per `bellman_ford::algo::bellman_ford`. A vertex still
+/// relaxed in pass V sits past a negative cycle, which walking back V predecessors lands on.
+fn bellman_ford_passes(edges: &[IntEdge], dist: &mut [Option<i128>], prev_edge: &mut [Option<IntEdge>]) -> Result<(), NegativeCycle> {
+    let n = dist.len();
+    for _ in 0..n.saturating_sub(1) {
+        let mut updated = false;
+        for &e in edges {
+            updated |= relax(e, dist, prev_edge);
+        }
+        if !updated { return Ok(()); }
+    }
+
+    let Some(relaxed) = edges.iter().find(|&&e| relax(e, dist, prev_edge)).map(|e| e.pair.target) else {
+        return Ok(());
+    };
+    let mut on_cycle = relaxed;
+    for _ in 0..n {
+        on_cycle = prev_edge[on_cycle].map_or(on_cycle, |e| e.pair.source);
+    }
+    let mut cycle = vec![];
+    let mut cur = on_cycle;
+    while let Some(e) = prev_edge[cur] {
+        cycle.push(e);
+        cur = e.pair.source;
+        if cur == on_cycle { break; }
+    }
+    cycle.reverse();
+    Err(NegativeCycle(cycle))
+}
+
+/// Bellman-Ford single source best paths, as per `bellman_ford::algo::bellman_ford`.
+pub(crate) fn bellman_ford(edges: &[IntEdge], source: usize) -> Result<BTreeMap<usize, Route>, NegativeCycle> {
+    let n = vertex_count(edges);
+    if source >= n {
+        return Ok(BTreeMap::new());
+    }
+
+    let mut dist = vec![None; n];
+    let mut prev_edge = vec![None; n];
+    dist[source] = Some(0);
+    bellman_ford_passes(edges, &mut dist, &mut prev_edge)?;
+
+    Ok((0..n).filter(|&target| dist[target].is_some()).map(|target| {
+        let mut route = vec![];
+        let mut cur = target;
+        while let (true, Some(e)) = (cur != source, prev_edge[cur]) {
+            route.push(e);
+            cur = e.pair.source;
+        }
+        route.reverse();
+        (target, route)
+    }).collect())
+}
+
+/// Bellman-Ford limited to paths of at most `max_hops` edges, as per `bellman_ford::algo::bellman_ford_hop_limited`.
+pub(crate) fn bellman_ford_hop_limited(edges: &[IntEdge], source: usize, max_hops: usize) -> BTreeMap<usize, Route> {
+    let n = vertex_count(edges);
+    if source >= n {
+        return BTreeMap::new();
+    }
+
+    let mut dist: Vec<Option<i128>> = vec![None; n];
+    dist[source] = Some(0);
+    let mut rounds: Vec<Vec<Option<IntEdge>>> = vec![];
+    for _ in 0..max_hops {
+        let prev_dist = dist.clone();
+        let mut relaxed = vec![None; n];
+        for &e in edges {
+            let Some(d) = prev_dist[e.pair.source] else { continue };
+            let new_d = d.saturating_add(e.cost);
+            if dist[e.pair.target].map_or(true, |old| new_d < old) {
+                dist[e.pair.target] = Some(new_d);
+                relaxed[e.pair.target] = Some(e);
+            }
+        }
+        if relaxed.iter().all(Option::is_none) { break; }
+        rounds.push(relaxed);
+    }
+
+    (0..n).filter(|&target| dist[target].is_some()).map(|target| {
+        let mut route = vec![];
+        let mut cur = target;
+        if target != source {
+            for relaxed in rounds.iter().rev() {
+                if let Some(e) = relaxed[cur] {
+                    route.push(e);
+                    cur = e.pair.source;
+                }
+            }
+        }
+        route.reverse();
+        (target, route)
+    }).collect()
+}
+
+/// Vertices touching any edge.
+fn present(edges: &[IntEdge], n: usize) -> Vec<bool> {
+    let mut present = vec![false; n];
+    for e in edges {
+        present[e.pair.source] = true;
+        present[e.pair.target] = true;
+    }
+    present
+}
+
+/// Floyd-Warshall all pairs best paths, as per `floyd_warshall::algo::floyd_warshall_tables`, with self references for
+/// every vertex present. Negative cycles are looked for up front, by Bellman-Ford passes from every vertex at once.
+pub(crate) fn floyd_warshall(edges: &[IntEdge]) -> Result<BTreeMap<Pair, Route>, NegativeCycle> {
+    let n = vertex_count(edges);
+    bellman_ford_passes(edges, &mut vec![Some(0); n], &mut vec![None; n])?;
+
+    let present = present(edges, n);
+    let mut cost: Vec<Option<i128>> = vec![None; n * n];
+    let mut next_edge: Vec<Option<IntEdge>> = vec![None; n * n];
+    for i in (0..n).filter(|&i| present[i]) {
+        cost[i * n + i] = Some(0);
+    }
+    for &e in edges.iter().filter(|e| e.pair.source != e.pair.target) {
+        cost[e.pair.source * n + e.pair.target] = Some(e.cost);
+        next_edge[e.pair.source * n + e.pair.target] = Some(e);
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(cost_ik) = cost[i * n + k] else { continue };
+            for j in 0..n {
+                let Some(cost_kj) = cost[k * n + j] else { continue };
+                let new_cost = cost_ik.saturating_add(cost_kj);
+                if cost[i * n + j].map_or(true, |old| new_cost < old) {
+                    cost[i * n + j] = Some(new_cost);
+                    next_edge[i * n + j] = next_edge[i * n + k];
+                }
+            }
+        }
+    }
+
+    let mut result = BTreeMap::new();
+    for i in 0..n {
+        for j in (0..n).filter(|&j| cost[i * n + j].is_some()) {
+            let mut route = vec![];
+            let mut cur = i;
+            while let (true, Some(e)) = (cur != j && route.len() < n, next_edge[cur * n + j]) {
+                route.push(e);
+                cur = e.pair.target;
+            }
+            result.insert(Pair { source: i, target: j }, route);
+        }
+    }
+    Ok(result)
+}
+
+/// All pairs best paths of at most `max_hops` edges, as per `bellman_ford::algo::all_pairs_hop_limited`.
+pub(crate) fn all_pairs_hop_limited(edges: &[IntEdge], max_hops: usize) -> BTreeMap<Pair, Route> {
+    let n = vertex_count(edges);
+    let present = present(edges, n);
+    (0..n).filter(|&source| present[source])
+        .flat_map(|source| bellman_ford_hop_limited(edges, source, max_hops).into_iter().map(move |(target, route)| (Pair { source, target }, route)))
+        .collect()
+}
@@ -0,0 +1,78 @@
+use super::algo::*;
+use super::calculator::SCALE;
+use super::super::wide::accumulate;
+use crate::types::{Accumulation, Objective, Rounding};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+fn edges(quotes: &[(usize, usize, u128)], accumulation: Accumulation, objective: Objective) -> Vec<IntEdge> {
+    let pairs = quotes.iter().map(|&(source, target, _)| Pair { source, target }).collect::<Vec<_>>();
+    let amounts = quotes.iter().map(|&(_, _, amount)| amount).collect::<Vec<_>>();
+    transform(&pairs, &amounts, SCALE, accumulation, objective)
+}
+
+/// Exact for powers of 2, otherwise at most 1 short of the true log.
+#[test]
+fn test_log2_fixed() {
+    for exp in [0, 1, 40, 63, 64, 127] {
+        assert_eq!((exp as i128) << FRAC_BITS, log2_fixed(1 << exp));
+    }
+    // f64 takes logs below 32 to the unit
+    for x in [3_u128, 10, 1_000, 123_456_789] {
+        let expected = (x as f64).log2() * (1_u64 << FRAC_BITS) as f64;
+        let log = log2_fixed(x) as f64;
+        assert!(log <= expected && expected - log < 2.0, "log2({x}) = {log} vs {expected}");
+    }
+    for x in [1_000_000_000_000_u128, 364_190_000_000_000, u128::MAX] {
+        let expected = (x as f64).log2() * (1_u64 << FRAC_BITS) as f64;
+        assert!((log2_fixed(x) as f64 - expected).abs() <= 8.0);
+    }
+}
+
+/// Best quote per pair, first of equal ones, zero rates dropped when multiplying.
+#[test]
+fn test_transform() {
+    let quotes = [(0, 1, 2 * SCALE), (0, 1, 3 * SCALE), (0, 1, 3 * SCALE), (1, 0, 0)];
+    let maximised = edges(&quotes, Accumulation::Product, Objective::Maximise);
+    assert_eq!(vec![1], maximised.iter().map(|e| e.quote).collect::<Vec<_>>());
+    let minimised = edges(&quotes, Accumulation::Sum, Objective::Minimise);
+    assert_eq!(vec![0, 3], minimised.iter().map(|e| e.quote).collect::<Vec<_>>());
+    assert_eq!(vec![2 * SCALE as i128, 0], minimised.iter().map(|e| e.cost).collect::<Vec<_>>());
+}
+
+/// Floyd-Warshall and Bellman-Ford from every source agree on all paths.
+#[test]
+fn test_parity() {
+    let quotes = [
+        (0, 1, 1_500_000_000_000), (1, 2, 600_000_000_000), (0, 2, 880_000_000_000), (2, 3, 2_000_000_000_000),
+        (3, 0, 300_000_000_000), (1, 3, 1_100_000_000_000), (3, 1, 500_000_000_000),
+    ];
+    let edges = edges(&quotes, Accumulation::Product, Objective::Maximise);
+    let all = floyd_warshall(&edges).unwrap();
+    assert_eq!(16, all.len());
+    for source in 0..4 {
+        for (target, route) in bellman_ford(&edges, source).unwrap() {
+            assert_eq!(all[&Pair { source, target }], route);
+        }
+    }
+    assert_eq!(vec![0, 1], all[&Pair { source: 0, target: 2 }].iter().map(|e| e.quote).collect::<Vec<_>>());
+    assert_eq!(all, all_pairs_hop_limited(&edges, 3));
+}
+
+/// Arbitrage is reported, a round trip of product exactly 1 is not.
+#[test]
+fn test_negative_cycle() {
+    let round_trip = [(0, 1, 1_250_000_000_000), (1, 0, 800_000_000_000), (1, 2, SCALE)];
+    let edges_round_trip = edges(&round_trip, Accumulation::Product, Objective::Maximise);
+    assert!(floyd_warshall(&edges_round_trip).is_ok());
+    assert!(bellman_ford(&edges_round_trip, 0).is_ok());
+    assert!(floyd_warshall(&edges(&round_trip, Accumulation::Product, Objective::Minimise)).is_ok());
+
+    let arbitrage = [(0, 1, 1_250_000_000_000), (1, 0, 800_000_000_001), (1, 2, SCALE)];
+    let edges_arbitrage = edges(&arbitrage, Accumulation::Product, Objective::Maximise);
+    let NegativeCycle(cycle) = floyd_warshall(&edges_arbitrage).unwrap_err();
+    assert_eq!(2, cycle.len());
+    assert_eq!(Some(1_000_000_000_001), accumulate(cycle.iter().map(|e| arbitrage[e.quote].2), SCALE, Accumulation::Product, Rounding::Down));
+    assert!(bellman_ford(&edges_arbitrage, 0).is_err());
+    assert!(bellman_ford(&edges_arbitrage, 2).unwrap().len() == 1);
+}
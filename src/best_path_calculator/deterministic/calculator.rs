@@ -0,0 +1,140 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use super::algo::{self, IntEdge, NegativeCycle, Route};
+use super::super::{PositionIndexer, currency_unit, wide};
+use crate::types::*;
+use crate::*;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Default fixed-point scale of amounts, as per `CalculatorOptions::scale`, as an integer.
+pub const SCALE: u128 = 1_000_000_000_000;
+
+/// Best paths by integer arithmetic only, for bit-identical results on every platform, eg. within a runtime where
+/// consensus depends on them.
+pub struct DeterministicCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> AllPairsBestPathCalculator<C, A, P> for DeterministicCalculator {
+
+    /// Calculates best paths for all currency pairs using Floyd-Warshall, as per `FloydWarshallCalculator`, over integer
+    /// costs. Products of rates are compared by fixed-point logs, with a resolution of 2^-48, and `total_cost` is their
+    /// exact product, rounded as per `rounding`. Steps carry the amounts as supplied.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::deterministic::calculator::DeterministicCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ];
+    /// let res = DeterministicCalculator::calc_best_paths(in_graph).unwrap();
+    /// assert_eq!(999_701_550_000_u128, res[&Pair { source: "BNB", target: "ETH" }].total_cost);
+    /// ```
+    fn calc_best_paths_with(options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let quotes = Quotes::new(options, pairs_and_prices)?;
+        let res = match options.max_hops {
+            Some(max_hops) => algo::all_pairs_hop_limited(&quotes.edges, max_hops),
+            None => algo::floyd_warshall(&quotes.edges).map_err(|cycle| quotes.to_calculator_error(cycle))?,
+        };
+        res.into_iter().map(|(algo::Pair { source, target }, route)| {
+            let pair = Pair { source: quotes.currency_indexer.by_ind(source), target: quotes.currency_indexer.by_ind(target) };
+            Ok((pair, quotes.to_price_path(source, route)?))
+        }).collect()
+    }
+}
+
+impl<C: Currency, A: Amount, P: Provider> SingleSourceBestPathCalculator<C, A, P> for DeterministicCalculator {
+
+    /// Calculates best paths from a single source currency using Bellman-Ford, as per `BellmanFordCalculator`, over integer
+    /// costs, as per `calc_best_paths_with`.
+    fn calc_best_paths_from_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let quotes = Quotes::new(options, pairs_and_prices)?;
+        let Some(source_idx) = quotes.currency_indexer.try_by_val(source) else {
+            return Ok(BTreeMap::new());
+        };
+        let res = match options.max_hops {
+            Some(max_hops) => algo::bellman_ford_hop_limited(&quotes.edges, source_idx, max_hops),
+            None => algo::bellman_ford(&quotes.edges, source_idx).map_err(|cycle| quotes.to_calculator_error(cycle))?,
+        };
+        res.into_iter().map(|(target, route)| {
+            let pair = Pair { source: source.clone(), target: quotes.currency_indexer.by_ind(target) };
+            Ok((pair, quotes.to_price_path(source_idx, route)?))
+        }).collect()
+    }
+}
+
+/// Input quotes, indexed, with their amounts as u128 and their best per pair as integer cost edges. Indexed directly
+/// rather than via `IndexedGraph`, which derives f64 rates.
+struct Quotes<'a, C: Currency, A: Amount, P: Provider> {
+    pairs_and_prices: &'a [(ProviderPair<C, P>, A)],
+    currency_indexer: PositionIndexer<'a, C>,
+    amounts: Vec<u128>,
+    /// unit per currency index, none without decimals
+    units: Vec<u128>,
+    edges: Vec<IntEdge>,
+    accumulation: Accumulation,
+    rounding: Rounding,
+    scale: u128,
+}
+
+impl<'a, C: Currency, A: Amount, P: Provider> Quotes<'a, C, A, P> {
+    fn new(options: &CalculatorOptions<C>, pairs_and_prices: &'a [(ProviderPair<C, P>, A)]) -> Result<Self, CalculatorError<C, A, P>> {
+        if options.scale == 0 {
+            return Err(CalculatorError::ConversionError);
+        }
+        let currency_indexer = PositionIndexer::new(pairs_and_prices.iter().flat_map(|(ProviderPair { pair: Pair { source, target }, .. }, _)| [source, target]));
+        let amounts = pairs_and_prices.iter()
+            .map(|(_, amount)| TryInto::<u128>::try_into(*amount).map_err(|_| CalculatorError::ConversionError))
+            .collect::<Result<Vec<_>, _>>()?;
+        let units = if options.decimals.is_empty() {
+            vec![]
+        } else {
+            (0..currency_indexer.len()).map(|ind| currency_unit(options, &currency_indexer.by_ind(ind))).collect::<Result<Vec<_>, _>>()?
+        };
+        // units of a path's currencies cancel out but for its ends, so only `total_cost` takes them, rather than the costs
+        let pairs = pairs_and_prices.iter()
+            .map(|(ProviderPair { pair, .. }, _)| algo::Pair { source: currency_indexer.by_val(&pair.source), target: currency_indexer.by_val(&pair.target) })
+            .collect::<Vec<_>>();
+        let edges = algo::transform(&pairs, &amounts, options.scale, options.accumulation, options.objective);
+        Ok(Self { pairs_and_prices, currency_indexer, amounts, units, edges, accumulation: options.accumulation, rounding: options.rounding, scale: options.scale })
+    }
+
+    /// Unwraps a route from `source`, failing with `OverflowError` of its pair if `total_cost` does not fit `A`.
+    fn to_price_path(&self, source: usize, route: Route) -> Result<PricePath<C, A, P>, CalculatorError<C, A, P>> {
+        let target = route.last().map_or(source, |e| e.pair.target);
+        let amounts = route.iter().map(|e| self.amounts[e.quote]);
+        let total_cost = match self.accumulation {
+            Accumulation::Product => wide::accumulate(core::iter::once(self.unit(source)).chain(amounts), self.scale, self.accumulation, self.rounding),
+            Accumulation::Sum => wide::accumulate(amounts, self.scale, self.accumulation, self.rounding),
+        }
+            .and_then(|total_cost| A::try_from(total_cost).ok())
+            .ok_or_else(|| CalculatorError::OverflowError(Pair {
+                source: self.currency_indexer.by_ind(source),
+                target: self.currency_indexer.by_ind(target),
+            }))?;
+        let steps = route.iter().map(|e| {
+            let (ProviderPair { pair, provider }, cost) = &self.pairs_and_prices[e.quote];
+            PathStep { pair: pair.clone(), provider: provider.clone(), cost: *cost }
+        }).collect();
+        Ok(PricePath { total_cost, steps })
+    }
+
+    /// Base units of one unit of a currency, as per `currency_unit`.
+    fn unit(&self, currency: usize) -> u128 {
+        self.units.get(currency).copied().unwrap_or(self.scale)
+    }
+
+    fn to_calculator_error(&self, NegativeCycle(cycle): NegativeCycle) -> CalculatorError<C, A, P> {
+        self.to_price_path(first_source(&cycle), cycle).map_or_else(|err| err, CalculatorError::NegativeCyclesError)
+    }
+}
+
+/// Source of the first step of a cycle.
+fn first_source(route: &Route) -> usize {
+    route.first().map_or(0, |e| e.pair.source)
+}
@@ -0,0 +1,78 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::PricePathGraph;
+use crate::{AllPairsBestPathCalculator, CalculatorOptions, SingleSourceBestPathCalculator};
+use super::calculator::{DeterministicCalculator, SCALE};
+use crate::best_path_calculator::floyd_warshall::calculator::FloydWarshallCalculator;
+use crate::best_path_calculator::fixtures::{quote, real_life_graph};
+
+/// Same paths as the floating point Floyd-Warshall, and total_cost too, as both recompute it exactly from the amounts supplied.
+#[test]
+fn test_parity_with_floyd_warshall() {
+    let graph = real_life_graph();
+    for options in [
+        CalculatorOptions::default(),
+        CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+        CalculatorOptions { max_hops: Some(2), ..Default::default() },
+        CalculatorOptions { decimals: ["BNB", "BTC", "DOT", "ETH", "USDT"].into_iter().zip([18, 8, 10, 18, 6]).map(|(c, d)| (c.to_owned(), d)).collect(), ..Default::default() },
+    ] {
+        let expected = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+        let res = DeterministicCalculator::calc_best_paths_with(&options, &graph).unwrap();
+        assert_eq!(expected, res);
+    }
+}
+
+/// Bellman-Ford from every source returns the Floyd-Warshall rows.
+#[test]
+fn test_single_source() {
+    let graph = real_life_graph();
+    let all = DeterministicCalculator::calc_best_paths(&graph).unwrap();
+    for source in ["BNB", "BTC", "DOT", "ETH", "USDT"] {
+        let res = DeterministicCalculator::calc_best_paths_from(&source.to_owned(), &graph).unwrap();
+        let expected = all.iter().filter(|(pair, _)| pair.source == source).map(|(pair, path)| (pair.clone(), path.clone())).collect::<PricePathGraph<_, _, _>>();
+        assert_eq!(expected, res);
+    }
+    assert!(DeterministicCalculator::calc_best_paths_from(&"XYZ".to_owned(), &graph).unwrap().is_empty());
+}
+
+/// Results are fixed to the unit, on any platform.
+#[test]
+fn test_exact_output() {
+    let res = DeterministicCalculator::calc_best_paths(&real_life_graph()).unwrap();
+    let total_cost = |source: &str, target: &str| res[&Pair { source: source.to_owned(), target: target.to_owned() }].total_cost;
+    assert_eq!(SCALE, total_cost("BNB", "BNB"));
+    // 17.43 * 0.002745 = 0.04784535
+    assert_eq!(47_845_350_000, total_cost("DOT", "BNB"));
+    // 17.43 * 0.0004192 * 0.06626 = 0.00048413902656, rounded down
+    assert_eq!(484_139_026, total_cost("DOT", "BTC"));
+    assert_eq!(vec![17_430_000_000_000, 419_200_000, 66_260_000_000],
+               res[&Pair { source: "DOT".to_owned(), target: "BTC".to_owned() }].steps.iter().map(|step| step.cost).collect::<Vec<_>>());
+}
+
+/// Arbitrage is reported with its exact profit ratio.
+#[test]
+fn test_negative_cycle_reported() {
+    let mut graph = real_life_graph();
+    graph.push(quote("USDT", "DOT", "Q", 0.06)); // 17.43 * 0.06 = 1.0458
+    for res in [DeterministicCalculator::calc_best_paths(&graph), DeterministicCalculator::calc_best_paths_from(&"BNB".to_owned(), &graph)] {
+        match res {
+            Err(CalculatorError::NegativeCyclesError(cycle)) => {
+                assert_eq!(2, cycle.steps.len());
+                assert_eq!(1_045_800_000_000, cycle.total_cost);
+            }
+            other => panic!("expected negative cycle, got {other:?}"),
+        }
+    }
+}
+
+/// Narrow amounts that cannot take the result fail rather than panic.
+#[test]
+fn test_overflow_error() {
+    let graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P" }, u64::MAX),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P" }, u64::MAX),
+    ];
+    assert_eq!(Err(CalculatorError::OverflowError(Pair { source: "A", target: "C" })), DeterministicCalculator::calc_best_paths(&graph));
+}
@@ -0,0 +1,136 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::{BTreeMap, BinaryHeap}, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BinaryHeap};
+use core::cmp::Ordering;
+pub(crate) use super::super::algo_types::{Edge, Path, adjacency_list, predecessor_paths, vertex_count};
+#[cfg(test)]
+pub(crate) use super::super::algo_types::Pair;
+
+/// Priority queue entry. Reversed ordering turns `BinaryHeap` into a min-heap by cost, ties broken by lowest vertex.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct State {
+    pub(crate) cost: f64,
+    pub(crate) vertex: usize,
+}
+impl Eq for State {}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.partial_cmp(&self.cost).unwrap()
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Dijkstra single-source shortest paths, for non-negative edge costs only. O(E log V).
+///
+/// Takes an adjacency list, see `adjacency_list`, so that it can be built once and queried from many sources.
+/// Same output as Bellman-Ford: `target_vertex → Path` for every vertex reachable from `source`, the source itself
+/// mapping to an empty path with zero cost.
+pub(crate) fn dijkstra(adjacency: &[Vec<Edge>], source: usize) -> BTreeMap<usize, Path> {
+    let n = adjacency.len();
+    if source >= n {
+        return BTreeMap::new();
+    }
+
+    // dist[v]      = best known cost source→v
+    // prev_edge[v] = incoming edge on the best known path to v
+    let mut dist      = vec![f64::INFINITY; n];
+    let mut prev_edge: Vec<Option<Edge>> = vec![None; n];
+    let mut heap = BinaryHeap::new();
+    dist[source] = 0.0;
+    heap.push(State { cost: 0.0, vertex: source });
+
+    while let Some(State { cost, vertex }) = heap.pop() {
+        // stale entry, vertex already settled at a lower cost
+        if cost > dist[vertex] { continue; }
+        for &e in &adjacency[vertex] {
+            let new_d = cost + e.cost;
+            if new_d < dist[e.pair.target] {
+                dist[e.pair.target] = new_d;
+                prev_edge[e.pair.target] = Some(e);
+                heap.push(State { cost: new_d, vertex: e.pair.target });
+            }
+        }
+    }
+
+    predecessor_paths(source, &dist, &prev_edge)
+}
+
+/// Bidirectional Dijkstra for a single pair, for non-negative edge costs only. None if `target` is unreachable.
+///
+/// Searches forward from `source` and backward from `target`, each time advancing the side with the cheaper frontier, and
+/// records the best meeting vertex whenever either side improves on one. Stops once the two frontiers together cannot beat
+/// that meeting, typically having settled only the vertices around both ends.
+pub(crate) fn bidirectional_dijkstra(edges: &[Edge], source: usize, target: usize) -> Option<Path> {
+    let n = vertex_count(edges);
+    if source >= n || target >= n || !edges.iter().any(|e| e.pair.source == source || e.pair.target == source) {
+        return None;
+    }
+    let forward = adjacency_list(edges, n);
+    let mut backward = vec![vec![]; n];
+    for &e in edges {
+        backward[e.pair.target].push(e);
+    }
+
+    // dist[0] and via[0] search forward, via[0][v] being the edge into v; dist[1] and via[1] backward, via[1][v] the edge out of v
+    let mut dist = [vec![f64::INFINITY; n], vec![f64::INFINITY; n]];
+    let mut via: [Vec<Option<Edge>>; 2] = [vec![None; n], vec![None; n]];
+    let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+    dist[0][source] = 0.0;
+    dist[1][target] = 0.0;
+    heaps[0].push(State { cost: 0.0, vertex: source });
+    heaps[1].push(State { cost: 0.0, vertex: target });
+    let (mut best, mut meeting) = if source == target { (0.0, Some(source)) } else { (f64::INFINITY, None) };
+
+    loop {
+        let fronts = [heaps[0].peek().map_or(f64::INFINITY, |s| s.cost), heaps[1].peek().map_or(f64::INFINITY, |s| s.cost)];
+        if fronts[0] + fronts[1] >= best {
+            break;
+        }
+        let side = if fronts[0] <= fronts[1] { 0 } else { 1 };
+        let State { cost, vertex } = heaps[side].pop().unwrap();
+        // stale entry, vertex already settled at a lower cost
+        if cost > dist[side][vertex] { continue; }
+        let adjacency = if side == 0 { &forward } else { &backward };
+        for &e in &adjacency[vertex] {
+            let next = if side == 0 { e.pair.target } else { e.pair.source };
+            let new_d = cost + e.cost;
+            if new_d < dist[side][next] {
+                dist[side][next] = new_d;
+                via[side][next] = Some(e);
+                heaps[side].push(State { cost: new_d, vertex: next });
+                if new_d + dist[1 - side][next] < best {
+                    best = new_d + dist[1 - side][next];
+                    meeting = Some(next);
+                }
+            }
+        }
+    }
+
+    let meeting = meeting?;
+    let mut edges = vec![];
+    let mut cur = meeting;
+    while let Some(e) = via[0][cur] {
+        edges.push(e);
+        cur = e.pair.source;
+    }
+    edges.reverse();
+    let mut cur = meeting;
+    while let Some(e) = via[1][cur] {
+        edges.push(e);
+        cur = e.pair.target;
+    }
+    Some(Path { total_cost: best, edges })
+}
+
+/// Convenience wrapper over `dijkstra` for a plain edge list.
+#[cfg(test)]
+pub(crate) fn dijkstra_from_edges(edges: &[Edge], source: usize) -> BTreeMap<usize, Path> {
+    dijkstra(&adjacency_list(edges, vertex_count(edges)), source)
+}
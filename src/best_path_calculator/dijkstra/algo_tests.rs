@@ -0,0 +1,59 @@
+use super::algo::*;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Same graph as the Floyd-Warshall youtube test — verify distances from source 0, see bellman_ford::algo_tests.
+#[test]
+fn test_youtube_distances() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 1, cost: 3.0 },
+        Edge { pair: Pair { source: 0, target: 3 }, provider: 1, cost: 7.0 },
+        Edge { pair: Pair { source: 1, target: 0 }, provider: 1, cost: 8.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 1, cost: 2.0 },
+        Edge { pair: Pair { source: 2, target: 0 }, provider: 1, cost: 5.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 1, cost: 1.0 },
+        Edge { pair: Pair { source: 3, target: 0 }, provider: 1, cost: 2.0 },
+    ];
+    let res = dijkstra_from_edges(&edges, 0);
+    assert_eq!(res[&0].total_cost, 0.0);
+    assert_eq!(res[&1].total_cost, 3.0);
+    assert_eq!(res[&2].total_cost, 5.0);
+    assert_eq!(res[&3].total_cost, 6.0);
+    assert_eq!(res[&3].edges, vec![edges[0], edges[3], edges[5]]);
+}
+
+/// Vertices unreachable from source are absent, unknown source returns empty.
+#[test]
+fn test_unreachable_and_unknown_source() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 1.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost: 1.0 },
+    ];
+    let res = dijkstra_from_edges(&edges, 0);
+    assert_eq!(vec![0, 1], res.keys().cloned().collect::<Vec<_>>());
+    assert!(dijkstra_from_edges(&edges, 99).is_empty());
+}
+
+/// Bidirectional search must agree with plain Dijkstra on every pair.
+#[test]
+fn test_bidirectional_parity() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 1, cost: 3.0 },
+        Edge { pair: Pair { source: 0, target: 3 }, provider: 1, cost: 7.0 },
+        Edge { pair: Pair { source: 1, target: 0 }, provider: 1, cost: 8.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 1, cost: 2.0 },
+        Edge { pair: Pair { source: 2, target: 0 }, provider: 1, cost: 5.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 1, cost: 1.0 },
+        Edge { pair: Pair { source: 3, target: 0 }, provider: 1, cost: 2.0 },
+        Edge { pair: Pair { source: 3, target: 4 }, provider: 1, cost: 0.5 },
+        Edge { pair: Pair { source: 5, target: 4 }, provider: 1, cost: 0.5 },
+    ];
+    for source in 0..7 {
+        let expected = dijkstra_from_edges(&edges, source);
+        for target in 0..7 {
+            let path = bidirectional_dijkstra(&edges, source, target);
+            assert_eq!(expected.get(&target).map(|path| path.edges.clone()), path.as_ref().map(|path| path.edges.clone()));
+            assert_eq!(expected.get(&target).map(|path| path.total_cost), path.map(|path| path.total_cost));
+        }
+    }
+}
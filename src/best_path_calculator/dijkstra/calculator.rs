@@ -0,0 +1,102 @@
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+use super::algo;
+use super::super::IndexedGraph;
+use super::super::algo_types::{TransformedGraph, adjacency_list, vertex_count};
+use super::super::bellman_ford::calculator::BellmanFordCalculator;
+use crate::types::*;
+use crate::*;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+pub use super::super::SCALE;
+
+pub struct DijkstraCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> SingleSourceBestPathCalculator<C, A, P> for DijkstraCalculator {
+
+    /// Calculates best paths from a single source currency using Dijkstra, as per `options`. O(E log V) vs Bellman-Ford's O(VE).
+    ///
+    /// Dijkstra requires non-negative shortest path costs, eg. all rates ≤ 1 when maximising a product of exchange rates,
+    /// or any costs when minimising a sum. This is checked up front, falling back to `BellmanFordCalculator` otherwise,
+    /// so the result is always as per Bellman-Ford, including negative cycle detection. Hop limited paths also use Bellman-Ford.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::dijkstra::calculator::DijkstraCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ];
+    /// // BNB -> USDT rate is > 1, hence resolved via Bellman-Ford
+    /// let res = DijkstraCalculator::calc_best_paths_from(&"BNB", in_graph).unwrap();
+    /// assert_eq!(999_701_550_000_u128, res.get(&Pair { source: "BNB", target: "ETH" }).unwrap().total_cost);
+    /// ```
+    fn calc_best_paths_from_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        if pairs_and_prices.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        let source_idx = match graph.currency_indexer.try_by_val(source) {
+            Some(idx) => idx,
+            None => return Ok(BTreeMap::new()),
+        };
+
+        let transformed = TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
+        if options.max_hops.is_some() || transformed.edges.iter().any(|e| e.cost < 0.0) {
+            return BellmanFordCalculator::calc_best_paths_from_with(options, source, pairs_and_prices);
+        }
+
+        let res = transformed.run(|edges| Ok(algo::dijkstra(&adjacency_list(edges, vertex_count(edges)), source_idx)))
+            .map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph_from(source_idx, res)
+    }
+}
+
+impl<C: Currency, A: Amount, P: Provider> SinglePairBestPathCalculator<C, A, P> for DijkstraCalculator {
+
+    /// Calculates the best path from `source` to `target` using bidirectional Dijkstra, as per `options`, searching from both
+    /// ends until the two searches settle the pair. Where costs may be negative, or with `max_hops`, falls back to
+    /// `BellmanFordCalculator`, which reports negative cycles affecting the pair.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::dijkstra::calculator::DijkstraCalculator;
+    ///
+    /// // latencies, summed and minimised
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P" }, 5_000_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P" }, 3_000_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "P" }, 9_000_000_000_000_u128),
+    /// ];
+    /// let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() };
+    /// let res = DijkstraCalculator::calc_best_path_with(&options, &"A", &"C", in_graph).unwrap().unwrap();
+    /// assert_eq!(8_000_000_000_000_u128, res.total_cost);
+    /// ```
+    fn calc_best_path_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<Option<PricePath<C, A, P>>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        let (Some(source_idx), Some(target_idx)) = (graph.currency_indexer.try_by_val(source), graph.currency_indexer.try_by_val(target)) else {
+            return Ok(None);
+        };
+
+        let transformed = TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
+        if options.max_hops.is_some() || transformed.edges.iter().any(|e| e.cost < 0.0) {
+            return BellmanFordCalculator::calc_best_path_with(options, source, target, pairs_and_prices);
+        }
+
+        let path = algo::bidirectional_dijkstra(&transformed.edges, source_idx, target_idx);
+        path.map(|path| graph.to_price_path(source_idx, transformed.restore(path))).transpose()
+    }
+}
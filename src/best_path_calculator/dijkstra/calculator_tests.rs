@@ -0,0 +1,92 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::{SinglePairBestPathCalculator, SingleSourceBestPathCalculator};
+use super::calculator::DijkstraCalculator;
+use crate::best_path_calculator::bellman_ford::calculator::BellmanFordCalculator;
+use crate::best_path_calculator::fixtures::quote;
+
+/// Rates all ≤ 1, with a shortcut via C beating the direct A → D quote.
+fn discount_graph() -> Vec<(ProviderPair<String, String>, u128)> {
+    vec![
+        quote("A", "B", "P", 0.5),
+        quote("B", "C", "P", 0.9),
+        quote("A", "C", "P", 0.6),
+        quote("C", "D", "P", 0.95),
+        quote("A", "D", "P", 0.5),
+        quote("A", "D", "Q", 0.55),
+        quote("D", "A", "P", 1.0),
+    ]
+}
+
+/// With rates ≤ 1, Dijkstra runs directly and must agree with Bellman-Ford.
+#[test]
+fn test_parity_with_bellman_ford() {
+    let graph = discount_graph();
+    for source in ["A", "B", "C", "D"] {
+        let source = source.to_owned();
+        assert_eq!(
+            BellmanFordCalculator::calc_best_paths_from(&source, &graph).unwrap(),
+            DijkstraCalculator::calc_best_paths_from(&source, &graph).unwrap(),
+        );
+    }
+    let res = DijkstraCalculator::calc_best_paths_from(&"A".to_owned(), &graph).unwrap();
+    let a_to_d = res.get(&Pair { source: "A".to_owned(), target: "D".to_owned() }).unwrap();
+    assert_eq!(vec!["C", "D"], a_to_d.steps.iter().map(|s| s.pair.target.as_str()).collect::<Vec<_>>());
+    assert_eq!(570_000_000_000, a_to_d.total_cost);
+}
+
+/// Minimising a sum never needs the fallback.
+#[test]
+fn test_sum_parity_with_bellman_ford() {
+    let graph = discount_graph();
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() };
+    let source = "B".to_owned();
+    assert_eq!(
+        BellmanFordCalculator::calc_best_paths_from_with(&options, &source, &graph).unwrap(),
+        DijkstraCalculator::calc_best_paths_from_with(&options, &source, &graph).unwrap(),
+    );
+}
+
+/// A rate > 1 breaks the precondition, results still as per Bellman-Ford, including arbitrage.
+#[test]
+fn test_falls_back_to_bellman_ford() {
+    let mut graph = discount_graph();
+    graph.push(quote("B", "D", "P", 1.5));
+    let source = "A".to_owned();
+    let res = DijkstraCalculator::calc_best_paths_from(&source, &graph).unwrap();
+    assert_eq!(BellmanFordCalculator::calc_best_paths_from(&source, &graph).unwrap(), res);
+    assert_eq!(750_000_000_000, res.get(&Pair { source: "A".to_owned(), target: "D".to_owned() }).unwrap().total_cost);
+
+    graph.push(quote("D", "B", "P", 1.0));
+    assert!(matches!(DijkstraCalculator::calc_best_paths_from(&source, &graph), Err(CalculatorError::NegativeCyclesError(_))));
+}
+
+#[test]
+fn test_unknown_source_returns_empty() {
+    let res = DijkstraCalculator::calc_best_paths_from(&"XYZ".to_owned(), &discount_graph()).unwrap();
+    assert!(res.is_empty());
+}
+
+/// With rates ≤ 1, the bidirectional search must agree with Bellman-Ford on every pair, otherwise it falls back to it.
+#[test]
+fn test_pair_parity_with_bellman_ford() {
+    let mut graph = discount_graph();
+    for _ in 0..2 {
+        for source in ["A", "B", "C", "D"] {
+            for target in ["A", "B", "C", "D"] {
+                let (source, target) = (source.to_owned(), target.to_owned());
+                assert_eq!(
+                    BellmanFordCalculator::calc_best_path(&source, &target, &graph).unwrap(),
+                    DijkstraCalculator::calc_best_path(&source, &target, &graph).unwrap(),
+                );
+            }
+        }
+        graph.push(quote("B", "D", "P", 1.5));
+    }
+
+    graph.push(quote("D", "B", "P", 1.0));
+    assert!(matches!(DijkstraCalculator::calc_best_path(&"A".to_owned(), &"D".to_owned(), &graph), Err(CalculatorError::NegativeCyclesError(_))));
+    assert_eq!(None, DijkstraCalculator::calc_best_path(&"A".to_owned(), &"XYZ".to_owned(), &graph).unwrap());
+}
@@ -0,0 +1,153 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+pub(crate) use super::super::algo_types::{Edge, Path, PathCalculationError, TransformedGraph, rank, vertex_count};
+use super::super::bellman_ford::algo::bellman_ford;
+use crate::types::{Accumulation, Disjointness, Objective};
+
+/// Up to `k` routes from `source` to `target` sharing no intermediate currency, or no provider, as per accumulation and
+/// objective, best first.
+pub(crate) fn disjoint_paths(
+    edges: &[Edge],
+    source: usize,
+    target: usize,
+    k: usize,
+    disjointness: Disjointness,
+    accumulation: Accumulation,
+    objective: Objective,
+) -> Result<Vec<Path>, PathCalculationError> {
+    let transformed = match disjointness {
+        Disjointness::Currency => TransformedGraph::new(edges, accumulation, objective),
+        Disjointness::Provider => TransformedGraph::per_provider(edges, accumulation, objective),
+    };
+    let paths = match disjointness {
+        Disjointness::Currency => currency_disjoint(&transformed.edges, source, target, k),
+        Disjointness::Provider => provider_disjoint(&transformed.edges, source, target, k),
+    }.map_err(|err| transformed.restore_error(err))?;
+    Ok(paths.into_iter().map(|path| transformed.restore(path)).collect())
+}
+
+/// Residual graph arc. Arcs are stored in pairs, so that the reverse of arc `i` is `i ^ 1`.
+#[derive(Copy, Clone, Debug)]
+struct Arc {
+    from: usize,
+    to: usize,
+    capacity: usize,
+    cost: f64,
+    /// Original edge, None for arcs splitting a vertex.
+    edge: Option<Edge>,
+}
+
+/// Suurballe's approach, generalised to `k` routes as successive shortest paths in a min cost flow.
+///
+/// Every vertex v is split into v_in (v) and v_out (v + n), joined by an arc of capacity 1, so that at most one route passes
+/// through it. Each edge u → v becomes u_out → v_in of capacity 1. Augmenting along the shortest residual path may cancel flow
+/// of earlier routes, rerouting them, so that the routes found are jointly best, ie. with the least total cost.
+/// Residual costs may be negative, hence Bellman-Ford, but never form a negative cycle provided the graph has none,
+/// which is checked up front.
+pub(crate) fn currency_disjoint(edges: &[Edge], source: usize, target: usize, k: usize) -> Result<Vec<Path>, PathCalculationError> {
+    let n = vertex_count(edges);
+    if k == 0 || source >= n || target >= n || source == target {
+        return Ok(vec![]);
+    }
+    bellman_ford(edges, source)?;
+
+    let mut arcs: Vec<Arc> = vec![];
+    let mut add_arc = |from: usize, to: usize, capacity: usize, cost: f64, edge: Option<Edge>| {
+        arcs.push(Arc { from, to, capacity, cost, edge });
+        arcs.push(Arc { from: to, to: from, capacity: 0, cost: -cost, edge });
+    };
+    for v in 0..n {
+        add_arc(v, v + n, if v == source || v == target { k } else { 1 }, 0.0, None);
+    }
+    // edges back into the source, or onwards from the target, cannot be part of a route
+    for &e in edges.iter().filter(|e| e.pair.target != source && e.pair.source != target && e.pair.source != e.pair.target) {
+        add_arc(e.pair.source + n, e.pair.target, 1, e.cost, Some(e));
+    }
+
+    let (from, to) = (source, target + n);
+    let mut routes = 0;
+    while routes < k {
+        match shortest_residual_path(&arcs, 2 * n, from, to) {
+            Some(path) => {
+                for ind in path {
+                    arcs[ind].capacity -= 1;
+                    arcs[ind ^ 1].capacity += 1;
+                }
+                routes += 1;
+            }
+            None => break,
+        }
+    }
+
+    // decompose the flow into routes, following saturated edge arcs from the source. As flow is conserved, one leaves every
+    // vertex of a route but the target; should float rounding ever break that, the route is dropped rather than walked on
+    let mut paths = vec![];
+    let mut used = vec![false; arcs.len()];
+    'routes: for _ in 0..routes {
+        let mut path_edges = vec![];
+        let mut cur = source;
+        while cur != target {
+            let next = (0..arcs.len()).step_by(2)
+                .filter(|&ind| !used[ind] && arcs[ind].from == cur + n && arcs[ind].capacity == 0)
+                .find_map(|ind| arcs[ind].edge.map(|e| (ind, e)));
+            let Some((ind, e)) = next else {
+                continue 'routes;
+            };
+            used[ind] = true;
+            path_edges.push(e);
+            cur = arcs[ind].to;
+        }
+        paths.push(Path { total_cost: path_edges.iter().map(|e| e.cost).sum(), edges: path_edges });
+    }
+    paths.sort_by(rank);
+    Ok(paths)
+}
+
+/// Bellman-Ford over residual arcs with spare capacity, returning the arc indices of the shortest path.
+fn shortest_residual_path(arcs: &[Arc], n: usize, from: usize, to: usize) -> Option<Vec<usize>> {
+    let mut dist = vec![f64::INFINITY; n];
+    let mut prev_arc: Vec<Option<usize>> = vec![None; n];
+    dist[from] = 0.0;
+    for _ in 0..n.saturating_sub(1) {
+        let mut updated = false;
+        for (ind, arc) in arcs.iter().enumerate() {
+            if arc.capacity == 0 || dist[arc.from].is_infinite() { continue; }
+            let new_d = dist[arc.from] + arc.cost;
+            if new_d < dist[arc.to] {
+                dist[arc.to] = new_d;
+                prev_arc[arc.to] = Some(ind);
+                updated = true;
+            }
+        }
+        if !updated { break; }
+    }
+
+    if dist[to].is_infinite() {
+        return None;
+    }
+    let mut path = vec![];
+    let mut cur = to;
+    while let Some(ind) = prev_arc[cur] {
+        path.push(ind);
+        cur = arcs[ind].from;
+        if cur == from { break; }
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Greedy: takes the best path, then excludes all of its providers before looking for the next one.
+/// Finding the jointly best provider disjoint routes is NP-hard, as providers colour edges across the whole graph.
+pub(crate) fn provider_disjoint(edges: &[Edge], source: usize, target: usize, k: usize) -> Result<Vec<Path>, PathCalculationError> {
+    let mut remaining = edges.to_vec();
+    let mut paths = vec![];
+    while paths.len() < k && source != target {
+        let path = match bellman_ford(&remaining, source)?.remove(&target) {
+            Some(path) => path,
+            None => break,
+        };
+        remaining.retain(|e| path.edges.iter().all(|used| used.provider != e.provider));
+        paths.push(path);
+    }
+    Ok(paths)
+}
@@ -0,0 +1,53 @@
+use super::algo::*;
+use crate::best_path_calculator::fixtures::edge;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// The shortest path 0→1→2→3 blocks any second route, whereas Suurballe reroutes to 0→1→3 and 0→2→3.
+#[test]
+fn test_currency_disjoint_reroutes() {
+    let edges = vec![
+        edge(0, 1, 0, 1.0),
+        edge(1, 2, 0, 1.0),
+        edge(2, 3, 0, 1.0),
+        edge(0, 2, 0, 2.5),
+        edge(1, 3, 0, 2.25),
+    ];
+    let paths = currency_disjoint(&edges, 0, 3, 2).unwrap();
+    assert_eq!(vec![vec![edges[0], edges[4]], vec![edges[3], edges[2]]], paths.iter().map(|p| p.edges.clone()).collect::<Vec<_>>());
+    assert_eq!(vec![3.25, 3.5], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+
+    // only 2 routes exist
+    assert_eq!(2, currency_disjoint(&edges, 0, 3, 5).unwrap().len());
+    assert_eq!(vec![edges[0], edges[1], edges[2]], currency_disjoint(&edges, 0, 3, 1).unwrap()[0].edges);
+}
+
+/// Negative costs are fine, a negative cycle is reported.
+#[test]
+fn test_currency_disjoint_negative_costs() {
+    let mut edges = vec![
+        edge(0, 1, 0, -1.0),
+        edge(1, 2, 0, -1.0),
+        edge(0, 2, 0, 0.5),
+    ];
+    let paths = currency_disjoint(&edges, 0, 2, 2).unwrap();
+    assert_eq!(vec![-2.0, 0.5], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+
+    edges.push(edge(2, 1, 0, 0.5));
+    assert!(matches!(currency_disjoint(&edges, 0, 2, 2), Err(PathCalculationError::NegativeCyclesError(_))));
+}
+
+/// Each next route avoids the providers of earlier ones.
+#[test]
+fn test_provider_disjoint() {
+    let edges = vec![
+        edge(0, 1, 0, 1.0),
+        edge(1, 2, 0, 1.0),
+        edge(1, 2, 1, 1.5),
+        edge(0, 1, 1, 2.0),
+        edge(0, 2, 2, 5.0),
+    ];
+    let paths = provider_disjoint(&edges, 0, 2, 5).unwrap();
+    assert_eq!(vec![2.0, 3.5, 5.0], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+    assert_eq!(vec![edges[3], edges[2]], paths[1].edges);
+}
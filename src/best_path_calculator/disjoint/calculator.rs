@@ -0,0 +1,63 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+use super::algo;
+use super::super::IndexedGraph;
+use crate::types::*;
+use crate::*;
+
+pub use super::super::SCALE;
+
+pub struct SuurballeCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> DisjointPathCalculator<C, A, P> for SuurballeCalculator {
+
+    /// Calculates up to `k` redundant routes from `source` to `target`, as per `options`, best first.
+    ///
+    /// With `Disjointness::Currency`, routes share no intermediate currency, nor a pair. Multiple providers per pair are
+    /// reduced to the best rate first, and routes are found with Suurballe's approach, generalised to `k` routes, so that
+    /// they are jointly best, ie. their overall cost is best, even if that makes the first route worse than the best path.
+    /// With `Disjointness::Provider`, routes share no provider. As that is NP-hard to optimise jointly, routes are found
+    /// greedily, each being the best path without the providers of routes found so far.
+    ///
+    /// Fewer than `k` routes are returned if no more exist. Fails with `UnsupportedOptionError` on `max_hops`, as a hop limit
+    /// does not fit the flow based search.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::disjoint::calculator::SuurballeCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "BINANCE" }, 364_000_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "BINANCE" }, 420_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "BTC" }, provider: "KRAKEN" }, 10_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "BTC", target: "ETH" }, provider: "KRAKEN" }, 15_000_000_000_000_u128),
+    /// ];
+    /// let routes = SuurballeCalculator::calc_disjoint_paths(&"BNB", &"ETH", 2, Disjointness::Currency, in_graph).unwrap();
+    /// assert_eq!(
+    ///     vec![152_880_000_000_u128, 150_000_000_000_u128],
+    ///     routes.iter().map(|r| r.total_cost).collect::<Vec<_>>()
+    /// );
+    /// ```
+    fn calc_disjoint_paths_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        k: usize,
+        disjointness: Disjointness,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>> {
+        if options.max_hops.is_some() {
+            return Err(CalculatorError::UnsupportedOptionError);
+        }
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        let (source_idx, target_idx) = match (graph.currency_indexer.try_by_val(source), graph.currency_indexer.try_by_val(target)) {
+            (Some(source_idx), Some(target_idx)) => (source_idx, target_idx),
+            _ => return Ok(vec![]),
+        };
+
+        let res = algo::disjoint_paths(&graph.edges, source_idx, target_idx, k, disjointness, options.accumulation, options.objective)
+            .map_err(|err| graph.to_calculator_error(err))?;
+        res.into_iter().map(|path| graph.to_price_path(source_idx, path)).collect()
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::DisjointPathCalculator;
+use super::calculator::{SuurballeCalculator, SCALE};
+use crate::best_path_calculator::fixtures::{quote, real_life_graph};
+
+/// As per `real_life_graph`, with quotes other than to or from USDT via Q, and one more via R.
+fn multi_provider_graph() -> Vec<(ProviderPair<String, String>, u128)> {
+    let mut graph = real_life_graph();
+    for (provider_pair, _) in graph.iter_mut().filter(|(pp, _)| pp.pair.source != "USDT" && pp.pair.target != "USDT") {
+        provider_pair.provider = "Q".to_owned();
+    }
+    graph.push(quote("BNB", "BTC", "R", 0.01));
+    graph
+}
+
+/// Routes share no intermediate currency, and each total is the product of its rates.
+#[test]
+fn test_currency_disjoint() {
+    let graph = multi_provider_graph();
+    let routes = SuurballeCalculator::calc_disjoint_paths(&"BNB".to_owned(), &"ETH".to_owned(), 5, Disjointness::Currency, &graph).unwrap();
+    assert_eq!(3, routes.len());
+    assert!(routes.windows(2).all(|w| w[0].total_cost >= w[1].total_cost));
+
+    let mut intermediates = routes.iter().flat_map(|r| r.steps[1..].iter().map(|s| s.pair.source.clone())).collect::<Vec<_>>();
+    let count = intermediates.len();
+    intermediates.sort();
+    intermediates.dedup();
+    assert_eq!(count, intermediates.len());
+
+    for route in &routes {
+        let product = route.steps.iter().fold(1.0, |acc, s| acc * s.cost as f64 / SCALE);
+        assert!((route.total_cost as f64 / SCALE - product).abs() < 1e-9);
+    }
+}
+
+/// Routes share no provider: direct via Q is best, leaving P via USDT, whereas the route via BTC needs Q too.
+#[test]
+fn test_provider_disjoint() {
+    let graph = multi_provider_graph();
+    let routes = SuurballeCalculator::calc_disjoint_paths(&"BNB".to_owned(), &"ETH".to_owned(), 5, Disjointness::Provider, &graph).unwrap();
+    assert_eq!(
+        vec![vec!["Q"], vec!["P", "P"]],
+        routes.iter().map(|r| r.steps.iter().map(|s| s.provider.as_str()).collect::<Vec<_>>()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_unknown_currency_returns_empty() {
+    let graph = multi_provider_graph();
+    assert!(SuurballeCalculator::calc_disjoint_paths(&"BNB".to_owned(), &"XYZ".to_owned(), 2, Disjointness::Currency, &graph).unwrap().is_empty());
+}
+
+#[test]
+fn test_max_hops_unsupported() {
+    let options = CalculatorOptions { max_hops: Some(2), ..Default::default() };
+    assert_eq!(
+        Err(CalculatorError::UnsupportedOptionError),
+        SuurballeCalculator::calc_disjoint_paths_with(&options, &"BNB".to_owned(), &"ETH".to_owned(), 2, Disjointness::Currency, &multi_provider_graph())
+    );
+}
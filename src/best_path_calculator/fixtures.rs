@@ -0,0 +1,39 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use super::SCALE;
+use super::algo_types::{self, Edge};
+
+/// A quote at `rate`, in scale of `SCALE`.
+pub(crate) fn quote(source: &str, target: &str, provider: &str, rate: f64) -> (ProviderPair<String, String>, u128) {
+    (
+        ProviderPair { pair: Pair { source: source.to_owned(), target: target.to_owned() }, provider: provider.to_owned() },
+        (rate * SCALE) as u128,
+    )
+}
+
+pub(crate) fn pair(source: &str, target: &str) -> Pair<String> {
+    Pair { source: source.to_owned(), target: target.to_owned() }
+}
+
+/// An edge between indexed currencies, as taken by the algorithms.
+pub(crate) fn edge(source: usize, target: usize, provider: usize, cost: f64) -> Edge {
+    Edge { pair: algo_types::Pair { source, target }, provider, cost }
+}
+
+pub(crate) fn real_life_graph() -> Vec<(ProviderPair<String, String>, u128)> {
+    vec![
+        quote("BTC",  "USDT", "P", 5997.42),
+        quote("USDT", "BTC",  "P", 0.00002777),
+        quote("ETH",  "USDT", "P", 2384.99),
+        quote("USDT", "ETH",  "P", 0.0004192),
+        quote("BNB",  "USDT", "P", 364.19),
+        quote("USDT", "BNB",  "P", 0.002745),
+        quote("DOT",  "USDT", "P", 17.43),
+        quote("USDT", "DOT",  "P", 0.05737),
+        quote("BTC",  "ETH",  "P", 15.09),
+        quote("ETH",  "BTC",  "P", 0.06626),
+        quote("ETH",  "BNB",  "P", 6.548),
+        quote("BNB",  "ETH",  "P", 0.1527),
+    ]
+}
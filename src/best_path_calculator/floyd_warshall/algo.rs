@@ -2,43 +2,51 @@
 use alloc::{collections::BTreeMap, vec, vec::Vec};
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
-use core::cmp::Ordering;
-#[cfg(not(feature = "std"))]
-#[allow(unused_imports)]
-use num_traits::Float;
-pub(crate) use super::super::algo_types::{Edge, Pair, Path, PathCalculationError, best_edge_per_pair};
+use core::ops::Range;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+pub(crate) use super::super::algo_types::{Edge, Pair, Path, PathCalculationError, TransformedGraph, WidePath, vertex_count, widest_edge_per_pair};
+pub(crate) use crate::types::{Accumulation, Objective};
+#[cfg(test)]
+pub(crate) use super::super::algo_types::best_edge_per_pair;
 
-/// Gets longest paths, as per: https://www.coursera.org/lecture/algorithms-on-graphs/currency-exchange-reduction-to-shortest-paths-cw8Tm
-/// - switch weights to log2(w) to allow for shortest_paths() addition of weights
-/// - negate log2(w) in order to reuse shortest_paths()
-///
-/// Formula, given: x*y = 2^(log2(x) + log2(y))
-/// maximizing x*y is equivalent to maximizing log2(x) + log2(y)
-/// ie. can convert weights x => log2(x), y => log2(y)
-/// Negate the weight for compatibility with shortest_path()
-pub (crate) fn longest_paths_by_product(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
-    let deduped = best_edge_per_pair(edges, Ordering::Greater);
-    // record the original weights before log-transforming
-    let weight_map: BTreeMap<(Pair, usize), f64> = deduped.iter().map(|e|((e.pair, e.provider), e.cost)).collect();
-    // map weights x => -log2(x) for use with shortest-path core
-    let log_edges: Vec<Edge> = deduped.iter().map(|e| Edge { cost: -e.cost.log2(), ..*e }).collect();
-
-    // call core directly — deduplication already done above, no second pass
-    let res = floyd_warshall_core(&log_edges)?;
-
-    // map costs back to original scale
-    Ok(res.into_iter().map(|(pair, path)| {
-        let edges_iter = path.edges.iter().map(|e| {
-            Edge { cost: weight_map[&(e.pair, e.provider)], ..*e }
-        });
-        let total_cost = edges_iter.clone().fold(1.0, |acc, e| acc * e.cost);
-        (pair, Path { total_cost, edges: edges_iter.collect::<Vec<_>>() })
-    }).collect::<BTreeMap<Pair, Path>>())
+/// Gets best paths for all pairs, as per `accumulation` and `objective`, running the shortest path core on the transformed graph.
+pub(crate) fn best_paths(edges: &[Edge], accumulation: Accumulation, objective: Objective) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    // call core directly — deduplication already done by TransformedGraph, no second pass
+    TransformedGraph::new(edges, accumulation, objective).run(|edges| floyd_warshall_core(edges, cfg!(feature = "parallel")))
+}
+
+/// As per `best_paths`, relaxing rows on the calling thread only, even with the `parallel` feature.
+pub(crate) fn best_paths_sequential(edges: &[Edge], accumulation: Accumulation, objective: Objective) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    TransformedGraph::new(edges, accumulation, objective).run(|edges| floyd_warshall_core(edges, false))
+}
+
+/// As per `best_paths`, over vertices 0..n, keeping the tables for paths to be reconstructed on lookup, along with the
+/// transformed graph to restore their costs.
+pub(crate) fn best_path_tables(edges: &[Edge], n: usize, accumulation: Accumulation, objective: Objective) -> Result<(TransformedGraph, Tables), PathCalculationError> {
+    let transformed = TransformedGraph::new(edges, accumulation, objective);
+    let tables = floyd_warshall_tables(&transformed.edges, n).map_err(|err| transformed.restore_error(err))?;
+    Ok((transformed, tables))
+}
+
+/// As per `best_paths`, running the cache-blocked core, for large graphs.
+pub(crate) fn best_paths_blocked(edges: &[Edge], accumulation: Accumulation, objective: Objective) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    TransformedGraph::new(edges, accumulation, objective).run(blocked_floyd_warshall_core)
+}
+
+/// Gets longest paths by product of rates for all pairs, as the arbitrage scanner's check for any profitable cycle.
+pub(crate) fn longest_paths_by_product(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    best_paths(edges, Accumulation::Product, Objective::Maximise)
+}
+
+#[cfg(test)]
+pub(crate) fn shortest_paths_by_product(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    best_paths(edges, Accumulation::Product, Objective::Minimise)
 }
 
 #[cfg(test)]
-pub (crate) fn shortest_paths(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
-    floyd_warshall_core(&best_edge_per_pair(edges, Ordering::Less))
+pub(crate) fn shortest_paths(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    best_paths(edges, Accumulation::Sum, Objective::Minimise)
 }
 
 // Floyd-Warshall core.
@@ -47,26 +55,72 @@ pub (crate) fn shortest_paths(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, Pa
 // guarantees dense 0..n IDs — no remapping needed). Paths reconstructed from the next-hop
 // table after the triple loop; no allocations inside the O(n³) iterations.
 // Row constants hoisted out of the j loop; updates are branchless (cmov-friendly).
-fn floyd_warshall_core(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+fn floyd_warshall_core(edges: &[Edge], parallel: bool) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
     if edges.is_empty() {
         return Ok(BTreeMap::new());
     }
 
     // vertex IDs are dense 0..n; derive n from the max ID present
-    let n = edges.iter()
-        .flat_map(|e| [e.pair.source, e.pair.target])
-        .max()
-        .map(|m| m + 1)
-        .unwrap_or(0);
-
-    // cost[i*n+j]     = best known cost i→j  (INFINITY = no path)
-    // next_hop[i*n+j] = first vertex after i on best path to j (usize::MAX = no path)
-    // edge_for[i*n+j] = direct edge i→j, for path reconstruction
+    Ok(floyd_warshall_tables_with(edges, vertex_count(edges), parallel)?.paths())
+}
+
+fn blocked_floyd_warshall_core(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    if edges.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(blocked_floyd_warshall_tables(edges, vertex_count(edges))?.paths())
+}
+
+/// Floyd-Warshall tables over vertices 0..n, from which best paths are reconstructed.
+/// Retained between updates by `IncrementalFloydWarshall`.
+#[derive(Clone, Debug)]
+pub(crate) struct Tables {
+    pub(crate) n: usize,
+    /// cost[i*n+j] = best known cost i→j (INFINITY = no path)
+    pub(crate) cost: Vec<f64>,
+    /// next_hop[i*n+j] = first vertex after i on best path to j (usize::MAX = no path)
+    pub(crate) next_hop: Vec<usize>,
+    /// edge_for[i*n+j] = direct edge i→j, for path reconstruction
+    pub(crate) edge_for: Vec<Option<Edge>>,
+    /// which vertex IDs actually appear, to avoid emitting phantom self-loops
+    pub(crate) vertex_exists: Vec<bool>,
+}
+
+impl Tables {
+    /// Best path i→j, None if there is none.
+    pub(crate) fn path(&self, i: usize, j: usize) -> Option<Path> {
+        let n = self.n;
+        if !self.path_exists(i, j) {
+            return None;
+        }
+        Some(Path { total_cost: self.cost[i * n + j], edges: reconstruct(&self.next_hop, &self.edge_for, n, i, j) })
+    }
+
+    /// Whether there is a best path i→j, without reconstructing it.
+    pub(crate) fn path_exists(&self, i: usize, j: usize) -> bool {
+        self.vertex_exists[i] && self.vertex_exists[j] && self.cost[i * self.n + j].is_finite()
+    }
+
+    /// Reconstructs full paths from the next-hop table — O(n²) total, done once.
+    pub(crate) fn paths(&self) -> BTreeMap<Pair, Path> {
+        let mut result: BTreeMap<Pair, Path> = BTreeMap::new();
+        for i in 0..self.n {
+            for j in 0..self.n {
+                if let Some(path) = self.path(i, j) {
+                    result.insert(Pair { source: i, target: j }, path);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Direct edges only, ie. the state before relaxing via any vertex. Fails on a negative self-loop, which is a cycle on
+/// its own, invisible to the scans for cycles through other vertices.
+fn initial_tables(edges: &[Edge], n: usize) -> Result<Tables, PathCalculationError> {
     let mut cost     = vec![f64::INFINITY; n * n];
     let mut next_hop = vec![usize::MAX;    n * n];
     let mut edge_for: Vec<Option<Edge>> = vec![None; n * n];
-
-    // track which vertex IDs actually appear, to avoid emitting phantom self-loops
     let mut vertex_exists = vec![false; n];
 
     for &e in edges {
@@ -81,55 +135,348 @@ fn floyd_warshall_core(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalcu
         edge_for[i * n + j] = Some(e);
     }
 
+    if let Some(&e) = edges.iter().find(|e| e.pair.source == e.pair.target && e.cost < 0.0) {
+        return Err(PathCalculationError::NegativeCyclesError(Path { total_cost: e.cost, edges: vec![e] }));
+    }
+    Ok(Tables { n, cost, next_hop, edge_for, vertex_exists })
+}
+
+/// Runs Floyd-Warshall over vertices 0..n, which must cover all vertex IDs in `edges`, at most one edge per pair.
+pub(crate) fn floyd_warshall_tables(edges: &[Edge], n: usize) -> Result<Tables, PathCalculationError> {
+    floyd_warshall_tables_with(edges, n, cfg!(feature = "parallel"))
+}
+
+/// As per `floyd_warshall_tables`, relaxing rows across threads only if `parallel`, given the `parallel` feature.
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+fn floyd_warshall_tables_with(edges: &[Edge], n: usize, parallel: bool) -> Result<Tables, PathCalculationError> {
+    let mut tables = initial_tables(edges, n)?;
+    let Tables { cost, next_hop, edge_for, .. } = &mut tables;
+
     // A[i,j] = min(A[i,j], A[i,k] + A[k,j])
     // Row constants hoisted; updates are branchless to enable cmov / auto-vectorisation.
     for k in 0..n {
+        // Before relaxing via k, the tables hold simple best paths through vertices < k only, so the first negative
+        // cycle to appear is some i→k→i. Rebuild it now, while both halves are still intact in the next-hop table.
         for i in 0..n {
-            let cost_ik = cost[i * n + k];
-            if cost_ik.is_infinite() { continue; }
-            let next_hop_ik = next_hop[i * n + k];
-            for j in 0..n {
-                let new_cost = cost_ik + cost[k * n + j];
-                let improved = new_cost < cost[i * n + j];
-                cost[i * n + j]     = if improved { new_cost }      else { cost[i * n + j] };
-                next_hop[i * n + j] = if improved { next_hop_ik }   else { next_hop[i * n + j] };
+            if cost[i * n + k] + cost[k * n + i] < 0.0 {
+                let mut walk = reconstruct(next_hop, edge_for, n, i, k);
+                walk.extend(reconstruct(next_hop, edge_for, n, k, i));
+                return Err(PathCalculationError::NegativeCyclesError(negative_simple_cycle(walk)));
             }
         }
+
+        // Row k holds, as cost[k,k] = 0 with no negative cycles, so rows can be relaxed independently against a copy of it.
+        let cost_k = cost[k * n..(k + 1) * n].to_vec();
+        #[cfg(feature = "parallel")]
+        if parallel && n >= PARALLEL_MIN_VERTICES {
+            cost.par_chunks_mut(n).zip(next_hop.par_chunks_mut(n)).for_each(|(cost_i, next_hop_i)| relax_row(cost_i, next_hop_i, k, &cost_k));
+            continue;
+        }
+        for (cost_i, next_hop_i) in cost.chunks_mut(n).zip(next_hop.chunks_mut(n)) {
+            relax_row(cost_i, next_hop_i, k, &cost_k);
+        }
     }
 
-    // check for negative cycles
-    for i in 0..n {
-        if vertex_exists[i] && cost[i * n + i] < 0.0 {
-            return Err(PathCalculationError::NegativeCyclesError);
+    Ok(tables)
+}
+
+/// Vertices per block of `blocked_floyd_warshall_tables`, such that the block's rows of costs fit in L2 for a few thousand vertices.
+const BLOCK: usize = 64;
+
+/// Cache-blocked Floyd-Warshall: relaxes via one block of vertices k at a time, first the rows of that block amongst
+/// themselves, then every other row against the block's rows, which stay in cache. The tables are thus streamed once per
+/// block, rather than once per vertex.
+/// Each row is relaxed via every k in the same order as per `floyd_warshall_tables`, against row k as it was on its turn,
+/// ie. before relaxing via the rest of its block. Costs and next hops are thus the same, including among equally good paths.
+/// On a negative cycle, falls back to `floyd_warshall_tables` to report it.
+pub(crate) fn blocked_floyd_warshall_tables(edges: &[Edge], n: usize) -> Result<Tables, PathCalculationError> {
+    let mut tables = initial_tables(edges, n)?;
+    let Tables { cost, next_hop, .. } = &mut tables;
+
+    for b0 in (0..n).step_by(BLOCK) {
+        let b1 = (b0 + BLOCK).min(n);
+        // rows of the block as of their turn, for the other rows to be relaxed against
+        let mut cost_b = Vec::with_capacity((b1 - b0) * n);
+        for k in b0..b1 {
+            let cost_k = cost[k * n..(k + 1) * n].to_vec();
+            for i in b0..b1 {
+                relax_row(&mut cost[i * n..(i + 1) * n], &mut next_hop[i * n..(i + 1) * n], k, &cost_k);
+            }
+            cost_b.extend(cost_k);
+        }
+        // a negative cycle through vertices up to this block shows on the diagonal, before costs run away
+        if (0..n).any(|i| cost[i * n + i] < 0.0) {
+            return floyd_warshall_tables(edges, n);
+        }
+
+        #[cfg(feature = "parallel")]
+        if n >= PARALLEL_MIN_VERTICES {
+            cost.par_chunks_mut(BLOCK * n).zip(next_hop.par_chunks_mut(BLOCK * n)).enumerate()
+                .filter(|(block, _)| block * BLOCK != b0)
+                .for_each(|(_, (cost_i, next_hop_i))| relax_block(cost_i, next_hop_i, n, b0..b1, &cost_b));
+            continue;
+        }
+        for (_, (cost_i, next_hop_i)) in cost.chunks_mut(BLOCK * n).zip(next_hop.chunks_mut(BLOCK * n)).enumerate().filter(|(block, _)| block * BLOCK != b0) {
+            relax_block(cost_i, next_hop_i, n, b0..b1, &cost_b);
         }
     }
+    if (0..n).any(|i| cost[i * n + i] < 0.0) {
+        return floyd_warshall_tables(edges, n);
+    }
 
-    // reconstruct full paths from the next-hop table — O(n²) total, done once
-    let mut result: BTreeMap<Pair, Path> = BTreeMap::new();
-    for i in 0..n {
-        if !vertex_exists[i] { continue; }
+    Ok(tables)
+}
+
+/// Smallest graph whose rows are relaxed across threads, below which spawning costs more than it saves.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_VERTICES: usize = 64;
+
+/// Relaxes row i via vertex k: A[i,j] = min(A[i,j], A[i,k] + A[k,j]).
+#[inline]
+fn relax_row(cost_i: &mut [f64], next_hop_i: &mut [usize], k: usize, cost_k: &[f64]) {
+    let cost_ik = cost_i[k];
+    if cost_ik.is_infinite() { return; }
+    let next_hop_ik = next_hop_i[k];
+    relax(cost_i, next_hop_i, cost_ik, next_hop_ik, cost_k);
+}
+
+/// Relaxes a block of rows, outside of block `ks`, via each vertex k of `ks` in turn. `cost_ks` holds the rows of `ks` as of
+/// their turn, small enough to stay in cache while each row is relaxed against all of them.
+fn relax_block(cost_i: &mut [f64], next_hop_i: &mut [usize], n: usize, ks: Range<usize>, cost_ks: &[f64]) {
+    for (cost_i, next_hop_i) in cost_i.chunks_mut(n).zip(next_hop_i.chunks_mut(n)) {
+        for (k, cost_k) in ks.clone().zip(cost_ks.chunks(n)) {
+            relax_row(cost_i, next_hop_i, k, cost_k);
+        }
+    }
+}
+
+/// A[i,j] = min(A[i,j], A[i,k] + A[k,j]) over a run of j. Branchless, to enable cmov / auto-vectorisation.
+#[inline]
+fn relax(cost_ij: &mut [f64], next_hop_ij: &mut [usize], cost_ik: f64, next_hop_ik: usize, cost_kj: &[f64]) {
+    for ((cost_ij, next_hop_ij), &cost_kj) in cost_ij.iter_mut().zip(next_hop_ij.iter_mut()).zip(cost_kj) {
+        let new_cost = cost_ik + cost_kj;
+        let improved = new_cost < *cost_ij;
+        *cost_ij     = if improved { new_cost }    else { *cost_ij };
+        *next_hop_ij = if improved { next_hop_ik } else { *next_hop_ij };
+    }
+}
+
+impl Tables {
+    /// Sets edge u→v, which must be cheaper than the current one, if any, updating best paths in O(n²).
+    /// Returns the pairs whose best path changed. If the edge closes a negative cycle, that is returned instead, with the
+    /// tables left untouched.
+    ///
+    /// Any improved path i→j is now i→u, u→v, v→j. Best paths into u and out of v are themselves unaffected, as they could
+    /// only use u→v by going around a cycle through it, which is negative if it improves anything.
+    pub(crate) fn improve(&mut self, e: Edge) -> Result<Vec<Pair>, PathCalculationError> {
+        let n = self.n;
+        let (u, v) = (e.pair.source, e.pair.target);
+        if u == v && e.cost < 0.0 {
+            return Err(PathCalculationError::NegativeCyclesError(Path { total_cost: e.cost, edges: vec![e] }));
+        }
+        if u != v && e.cost + self.cost[v * n + u] < 0.0 {
+            let mut walk = vec![e];
+            walk.extend(reconstruct(&self.next_hop, &self.edge_for, n, v, u));
+            return Err(PathCalculationError::NegativeCyclesError(negative_simple_cycle(walk)));
+        }
+
+        let mut changed = vec![];
+        for w in [u, v] {
+            if !self.vertex_exists[w] {
+                self.vertex_exists[w] = true;
+                self.cost[w * n + w] = 0.0;
+                changed.push(Pair { source: w, target: w });
+            }
+        }
+        self.edge_for[u * n + v] = Some(e);
+        if u == v {
+            return Ok(changed);
+        }
+
+        let into_u = (0..n).map(|i| self.cost[i * n + u]).collect::<Vec<_>>();
+        let out_of_v = self.cost[v * n..(v + 1) * n].to_vec();
+        for (i, &cost_iu) in into_u.iter().enumerate() {
+            if cost_iu.is_infinite() { continue; }
+            let next_hop_iu = if i == u { v } else { self.next_hop[i * n + u] };
+            for (j, &cost_vj) in out_of_v.iter().enumerate() {
+                let new_cost = cost_iu + e.cost + cost_vj;
+                if new_cost < self.cost[i * n + j] {
+                    self.cost[i * n + j] = new_cost;
+                    self.next_hop[i * n + j] = next_hop_iu;
+                    changed.push(Pair { source: i, target: j });
+                }
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Sources whose best path to any target takes the direct edge for any of `pairs`, in O(n²).
+    pub(crate) fn rows_using(&self, pairs: &[Pair]) -> Vec<usize> {
+        let n = self.n;
+        let mut using = vec![false; n];
+        for j in 0..n {
+            let mut marked = vec![None; n];
+            for pair in pairs.iter().filter(|pair| pair.source != j && self.next_hop[pair.source * n + j] == pair.target) {
+                marked[pair.source] = Some(true);
+            }
+            if marked.iter().all(Option::is_none) { continue; }
+            for (using, leads) in using.iter_mut().zip(self.leads_through(j, marked)) {
+                *using |= leads;
+            }
+        }
+        (0..n).filter(|&i| using[i]).collect()
+    }
+
+    /// Next hops of `rows`, along with whether each path exists, for `changed_rows_since` once they are replaced.
+    pub(crate) fn rows(&self, rows: &[usize]) -> BTreeMap<usize, Vec<(usize, bool)>> {
+        let n = self.n;
+        rows.iter().map(|&i| (i, (0..n).map(|j| (self.next_hop[i * n + j], self.path_exists(i, j))).collect())).collect()
+    }
+
+    /// Replaces row `i` with single source best paths from `i`, eg. as per Bellman-Ford.
+    pub(crate) fn set_row(&mut self, i: usize, paths: &BTreeMap<usize, Path>) {
+        let n = self.n;
+        self.cost[i * n..(i + 1) * n].fill(f64::INFINITY);
+        self.next_hop[i * n..(i + 1) * n].fill(usize::MAX);
+        for (&j, path) in paths {
+            self.cost[i * n + j] = path.total_cost;
+            if let Some(first) = path.edges.first() {
+                self.next_hop[i * n + j] = first.pair.target;
+            }
+        }
+    }
+
+    /// Pairs whose best path differs since `previous` rows were replaced, and the direct edges for `pairs` reset, in O(n²).
+    /// A path differs exactly if it leads through a source whose own entry differs, or which takes one of `pairs`.
+    pub(crate) fn changed_rows_since(&self, previous: &BTreeMap<usize, Vec<(usize, bool)>>, pairs: &[Pair]) -> Vec<Pair> {
+        let n = self.n;
+        let mut changed = vec![];
         for j in 0..n {
-            if !vertex_exists[j] { continue; }
-            if cost[i * n + j].is_infinite() { continue; }
-            let pair = Pair { source: i, target: j };
-            let path_edges = if i == j {
-                vec![]
-            } else {
-                let mut hops = vec![];
-                let mut cur = i;
-                while cur != j {
-                    let nxt = next_hop[cur * n + j];
-                    if nxt == usize::MAX { break; }
-                    if let Some(e) = edge_for[cur * n + nxt] {
-                        hops.push(e);
-                    }
-                    cur = nxt;
+            let mut marked = vec![None; n];
+            for (&i, row) in previous {
+                let next_hop = self.next_hop[i * n + j];
+                if row[j] != (next_hop, self.path_exists(i, j)) || pairs.contains(&Pair { source: i, target: next_hop }) {
+                    marked[i] = Some(true);
                 }
-                hops
+            }
+            if marked.iter().all(Option::is_none) { continue; }
+            changed.extend(self.leads_through(j, marked).into_iter().enumerate().filter(|&(_, leads)| leads).map(|(source, _)| Pair { source, target: j }));
+        }
+        changed
+    }
+
+    /// Per source, whether its best path to `j` leads through a source `marked` true. Best paths into `j` form a tree along
+    /// the next hops of its column, so each source is resolved once, by following them up to one already resolved.
+    fn leads_through(&self, j: usize, mut marked: Vec<Option<bool>>) -> Vec<bool> {
+        let n = self.n;
+        let mut chain = vec![];
+        for i in 0..n {
+            let mut cur = i;
+            let leads = loop {
+                if let Some(leads) = marked[cur] { break leads; }
+                chain.push(cur);
+                let nxt = self.next_hop[cur * n + j];
+                if cur == j || nxt == usize::MAX { break false; }
+                cur = nxt;
             };
-            result.insert(pair, Path { total_cost: cost[i * n + j], edges: path_edges });
+            for v in chain.drain(..) {
+                marked[v] = Some(leads);
+            }
+        }
+        marked.into_iter().map(|leads| leads == Some(true)).collect()
+    }
+
+    /// Pairs whose best path differs between `previous` and `self`, covering vertices of both.
+    pub(crate) fn changed_since(&self, previous: &Tables) -> Vec<Pair> {
+        let mut changed = vec![];
+        for source in 0..self.n {
+            for target in 0..self.n {
+                let previous_path = if source < previous.n && target < previous.n { previous.path(source, target) } else { None };
+                if previous_path.map(|path| path.edges) != self.path(source, target).map(|path| path.edges) {
+                    changed.push(Pair { source, target });
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// Widest paths for all pairs, maximising the smallest capacity along the path, with `capacities` as per `edges`.
+///
+/// Same structure as `floyd_warshall_core`, with max-min in place of min-sum: A[i,j] = max(A[i,j], min(A[i,k], A[k,j])).
+/// Capacities cannot improve around a cycle, so there is no notion of negative cycles. Only strict improvements replace
+/// a path, hence among equally wide paths the first found is kept, regardless of rates. Self references are left out.
+pub(crate) fn widest_paths(edges: &[Edge], capacities: &[u128]) -> BTreeMap<Pair, WidePath> {
+    let widest = widest_edge_per_pair(edges, capacities);
+    let n = vertex_count(&widest.iter().map(|(e, _)| *e).collect::<Vec<_>>());
+
+    // width[i*n+j] = widest known capacity i→j (0 = no path), next_hop and edge_for as per floyd_warshall_core
+    let mut width    = vec![0_u128;     n * n];
+    let mut next_hop = vec![usize::MAX; n * n];
+    let mut edge_for: Vec<Option<Edge>> = vec![None; n * n];
+
+    for &(e, capacity) in widest.iter().filter(|(e, _)| e.pair.source != e.pair.target) {
+        let (i, j) = (e.pair.source, e.pair.target);
+        width[i * n + j]    = capacity;
+        next_hop[i * n + j] = j;
+        edge_for[i * n + j] = Some(e);
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let width_ik = width[i * n + k];
+            if width_ik == 0 || i == k { continue; }
+            let next_hop_ik = next_hop[i * n + k];
+            for j in 0..n {
+                let new_width = width_ik.min(width[k * n + j]);
+                if i != j && new_width > width[i * n + j] {
+                    width[i * n + j]    = new_width;
+                    next_hop[i * n + j] = next_hop_ik;
+                }
+            }
         }
     }
 
-    Ok(result)
+    let mut result = BTreeMap::new();
+    for i in 0..n {
+        for j in 0..n {
+            if width[i * n + j] == 0 { continue; }
+            result.insert(Pair { source: i, target: j }, WidePath { capacity: width[i * n + j], edges: reconstruct(&next_hop, &edge_for, n, i, j) });
+        }
+    }
+    result
+}
+
+/// Follows the next-hop table from `i` to `j`, collecting the direct edges along the way. Empty for `i == j`.
+fn reconstruct(next_hop: &[usize], edge_for: &[Option<Edge>], n: usize, i: usize, j: usize) -> Vec<Edge> {
+    let mut hops = vec![];
+    let mut cur = i;
+    while cur != j {
+        let nxt = next_hop[cur * n + j];
+        if nxt == usize::MAX { break; }
+        if let Some(e) = edge_for[cur * n + nxt] {
+            hops.push(e);
+        }
+        cur = nxt;
+    }
+    hops
+}
+
+/// Reduces a closed walk of negative total cost to an elementary cycle, also of negative cost.
+/// Whenever the walk revisits a vertex, the loop in between is split off: returned if negative, dropped otherwise,
+/// which leaves the remainder of the walk still negative.
+fn negative_simple_cycle(walk: Vec<Edge>) -> Path {
+    let mut stack: Vec<Edge> = vec![];
+    for &e in &walk {
+        stack.push(e);
+        if let Some(pos) = stack.iter().position(|x| x.pair.source == e.pair.target) {
+            let total_cost = stack[pos..].iter().map(|x| x.cost).sum::<f64>();
+            if total_cost < 0.0 {
+                return Path { total_cost, edges: stack[pos..].to_vec() };
+            }
+            stack.truncate(pos);
+        }
+    }
+    // only reachable through float rounding of near-zero loops, report the walk as is
+    Path { total_cost: walk.iter().map(|x| x.cost).sum(), edges: walk }
 }
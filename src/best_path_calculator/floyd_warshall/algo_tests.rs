@@ -37,6 +37,21 @@ fn test_unique_edges() {
     assert_eq!(10.0, set.iter().next().unwrap().cost);
 }
 
+/// Of multiple providers per pair, the one with the best rate is kept.
+#[test]
+fn test_dedup_keeps_best_rate() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 2.0},
+        Edge{pair: Pair{source: 0, target: 1}, provider: 2, cost: 3.0},
+        Edge{pair: Pair{source: 0, target: 1}, provider: 3, cost: 1.5},
+    ];
+    let res = longest_paths_by_product(&edges).unwrap();
+    assert_eq!(
+        Some(&Path { total_cost: 3.0, edges: vec![Edge { pair: Pair { source: 0, target: 1 }, provider: 2, cost: 3.0 }] }),
+        res.get(&Pair { source: 0, target: 1 })
+    );
+}
+
 /// Graph
 ///      .5     2        .25    4
 /// 0. *----------* 1. *----------* 2.
@@ -117,3 +132,170 @@ fn test_youtube_negative_cycle() {
     ];
     assert!(shortest_paths(&edges).is_err());
 }
+
+#[test]
+fn test_youtube_negative_cycle_path() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 3.0},
+        Edge{pair: Pair{source: 0, target: 3}, provider: 1, cost: 7.0},
+        Edge{pair: Pair{source: 1, target: 0}, provider: 1, cost: 8.0},
+        Edge{pair: Pair{source: 1, target: 2}, provider: 1, cost: 2.0},
+        Edge{pair: Pair{source: 2, target: 0}, provider: 1, cost: -6.0},  // causes negative cycle
+        Edge{pair: Pair{source: 2, target: 3}, provider: 1, cost: 1.0},
+        Edge{pair: Pair{source: 3, target: 0}, provider: 1, cost: 2.0},
+    ];
+    match shortest_paths(&edges) {
+        Err(PathCalculationError::NegativeCyclesError(cycle)) => {
+            assert_eq!(-1.0, cycle.total_cost);
+            assert_eq!(vec![
+                Edge { pair: Pair { source: 2, target: 0 }, provider: 1, cost: -6.0 },
+                Edge { pair: Pair { source: 0, target: 1 }, provider: 1, cost: 3.0 },
+                Edge { pair: Pair { source: 1, target: 2 }, provider: 1, cost: 2.0 }], cycle.edges);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+/// Graph with a profitable round trip: 2.0 * 0.6 = 1.2
+#[test]
+fn test_arbitrage_cycle_in_original_scale() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 2.0},
+        Edge{pair: Pair{source: 1, target: 0}, provider: 1, cost: 0.6},
+    ];
+    match longest_paths_by_product(&edges) {
+        Err(PathCalculationError::NegativeCyclesError(cycle)) => {
+            assert_eq!(1.2, cycle.total_cost);
+            assert_eq!(vec![
+                Edge { pair: Pair { source: 1, target: 0 }, provider: 1, cost: 0.6 },
+                Edge { pair: Pair { source: 0, target: 1 }, provider: 1, cost: 2.0 }], cycle.edges);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+/// Cost factors, smallest product wins: 0 → 1 → 2 at 0.5 * 1.5 = 0.75 beats direct 0.8, the zero quote is ignored.
+#[test]
+fn test_shortest_paths_by_product() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 0.5},
+        Edge{pair: Pair{source: 1, target: 2}, provider: 1, cost: 1.5},
+        Edge{pair: Pair{source: 0, target: 2}, provider: 1, cost: 0.8},
+        Edge{pair: Pair{source: 0, target: 2}, provider: 2, cost: 0.0},  // missing quote
+    ];
+    let res = shortest_paths_by_product(&edges).unwrap();
+    assert_eq!(Path { total_cost: 0.75, edges: vec![edges[0], edges[1]] }, res[&Pair { source: 0, target: 2 }]);
+    assert_eq!(Path { total_cost: 1.0, edges: vec![] }, res[&Pair { source: 0, target: 0 }]);
+}
+
+/// Round trip multiplying to 0.5 * 1.5 = 0.75 < 1 keeps shrinking the product.
+#[test]
+fn test_shortest_paths_by_product_cycle() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 0.5},
+        Edge{pair: Pair{source: 1, target: 0}, provider: 1, cost: 1.5},
+    ];
+    match shortest_paths_by_product(&edges) {
+        Err(PathCalculationError::NegativeCyclesError(cycle)) => assert_eq!(0.75, cycle.total_cost),
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+/// Widest path 0→1→3 at min(8, 6) beats the direct 0→3 at 5 and 0→2→3 at min(9, 2).
+#[test]
+fn test_widest_paths() {
+    let edges = [
+        Edge{pair: Pair{source: 0, target: 1}, provider: 0, cost: 1.0},
+        Edge{pair: Pair{source: 1, target: 3}, provider: 0, cost: 1.0},
+        Edge{pair: Pair{source: 0, target: 3}, provider: 0, cost: 1.0},
+        Edge{pair: Pair{source: 0, target: 3}, provider: 1, cost: 1.0},
+        Edge{pair: Pair{source: 0, target: 2}, provider: 0, cost: 1.0},
+        Edge{pair: Pair{source: 2, target: 3}, provider: 0, cost: 1.0},
+        Edge{pair: Pair{source: 3, target: 0}, provider: 0, cost: 1.0},
+    ];
+    let res = widest_paths(&edges, &[8, 6, 5, 0, 9, 2, 100]);
+    assert_eq!(WidePath { capacity: 6, edges: vec![edges[0], edges[1]] }, res[&Pair { source: 0, target: 3 }]);
+    assert_eq!(WidePath { capacity: 8, edges: vec![edges[6], edges[0]] }, res[&Pair { source: 3, target: 1 }]);
+    assert_eq!(2, res[&Pair { source: 2, target: 1 }].capacity);
+    assert!(!res.contains_key(&Pair { source: 0, target: 0 }));
+    assert_eq!(12, res.len());
+}
+
+/// SplitMix64 finaliser, mapped to [0, 1).
+fn pseudo_random(seed: u64) -> f64 {
+    let mut x = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (x ^ (x >> 31)) as f64 / u64::MAX as f64
+}
+
+/// Sparse graph of `n` vertices with pseudo-random costs in [1, 2), so that there are no ties.
+fn pseudo_random_graph(n: usize) -> Vec<Edge> {
+    (0..n).flat_map(|i| [(i + 1) % n, (i * 7 + 3) % n, (i * 13 + 5) % n].map(|j| (i, j)))
+        .filter(|&(i, j)| i != j)
+        .enumerate()
+        .map(|(ind, (i, j))| Edge{pair: Pair{source: i, target: j}, provider: 0, cost: 1.0 + pseudo_random(ind as u64)})
+        .collect()
+}
+
+/// Large enough for rows to be relaxed across threads with the `parallel` feature, paths must match Bellman-Ford from every
+/// source.
+#[test]
+fn test_shortest_paths_large_graph() {
+    let n = 100;
+    let edges = pseudo_random_graph(n);
+    let paths = shortest_paths(&edges).unwrap();
+
+    for source in 0..n {
+        let from_source = crate::best_path_calculator::bellman_ford::algo::bellman_ford(&edges, source).unwrap();
+        for (target, expected) in from_source {
+            let path = &paths[&Pair{source, target}];
+            assert_eq!(expected.edges, path.edges);
+            assert!((expected.total_cost - path.total_cost).abs() < 1e-9);
+        }
+    }
+    assert_eq!(n * n, paths.len());
+}
+
+/// Rows relaxed across threads, with the `parallel` feature, must give the very same paths as on a single thread.
+#[test]
+fn test_sequential_parity() {
+    let edges = pseudo_random_graph(100);
+    assert_eq!(best_paths(&edges, Accumulation::Sum, Objective::Minimise).unwrap(), best_paths_sequential(&edges, Accumulation::Sum, Objective::Minimise).unwrap());
+}
+
+/// Over several blocks, the last one partial, the blocked core must find the very same paths.
+#[test]
+fn test_blocked_parity() {
+    for n in [1, 2, 64, 150] {
+        let edges = pseudo_random_graph(n);
+        let expected = floyd_warshall_tables(&edges, n).unwrap();
+        let blocked = blocked_floyd_warshall_tables(&edges, n).unwrap();
+        assert_eq!(expected.next_hop, blocked.next_hop);
+        assert_eq!(expected.paths().into_iter().map(|(pair, path)| (pair, path.edges)).collect::<Vec<_>>(),
+                   blocked.paths().into_iter().map(|(pair, path)| (pair, path.edges)).collect::<Vec<_>>());
+    }
+}
+
+/// With all rates equal, most pairs have several best paths, of which the blocked core must choose the same.
+#[test]
+fn test_blocked_parity_with_ties() {
+    let n = 150;
+    let edges = pseudo_random_graph(n).into_iter().map(|e| Edge { cost: 1.0, ..e }).collect::<Vec<_>>();
+    let expected = floyd_warshall_tables(&edges, n).unwrap();
+    let blocked = blocked_floyd_warshall_tables(&edges, n).unwrap();
+    assert_eq!(expected.cost, blocked.cost);
+    assert_eq!(expected.next_hop, blocked.next_hop);
+}
+
+/// The blocked core reports the same negative cycle, whichever block closes it.
+#[test]
+fn test_blocked_negative_cycle() {
+    let n = 150;
+    let mut edges = pseudo_random_graph(n);
+    edges.push(Edge{pair: Pair{source: 140, target: 3}, provider: 0, cost: -20.0});
+    let PathCalculationError::NegativeCyclesError(expected) = floyd_warshall_tables(&edges, n).unwrap_err();
+    let PathCalculationError::NegativeCyclesError(cycle) = blocked_floyd_warshall_tables(&edges, n).unwrap_err();
+    assert_eq!(expected, cycle);
+    assert!(cycle.total_cost < 0.0);
+}
@@ -2,16 +2,15 @@
 #[allow(unused_imports)]
 use alloc::{
     borrow::ToOwned,
-    collections::BTreeMap,
     string::String,
     vec, vec::Vec,
 };
 use super::algo;
-use super::super::PositionIndexer;
+use super::lazy::LazyPricePathGraph;
+use super::super::IndexedGraph;
+use super::super::bellman_ford::algo::best_paths_hop_limited;
 use crate::types::*;
 use crate::*;
-#[cfg(feature = "std")]
-use std::collections::BTreeMap;
 
 pub use super::super::SCALE;
 
@@ -19,10 +18,13 @@ pub struct FloydWarshallCalculator {}
 
 impl<C: Currency, A: Amount, P: Provider> AllPairsBestPathCalculator<C, A, P> for FloydWarshallCalculator {
 
-    /// Calculates best paths for all currency pairs using Floyd-Warshall.
+    /// Calculates best paths for all currency pairs using Floyd-Warshall, as per `options`.
     /// Accepts graph represented with trait Currency, Amount, Provider and wraps these into primitive indexed internal representations. Unwraps back on exit.
     ///
-    /// Typical usage below. Note all prices are in scale of 10^12, including self references, eg. cost of BNB -> BNB = 10^12
+    /// Typical usage below, with default options maximising the product of exchange rates.
+    /// Note all prices are in scale of `options.scale`, 10^12 by default, including self references, eg. cost of BNB -> BNB = 10^12.
+    /// When summing costs instead, self references cost 0.
+    /// With `max_hops`, paths are found by Bellman-Ford from every source instead, limited to that many rounds.
     /// ```rust
     /// # use best_path::prelude::*;
     /// # use best_path::prelude::floyd_warshall::calculator::FloydWarshallCalculator;
@@ -47,30 +49,121 @@ impl<C: Currency, A: Amount, P: Provider> AllPairsBestPathCalculator<C, A, P> fo
     ///     res_ref.get(&Pair { source: "BNB".to_owned(), target: "ETH".to_owned() }).unwrap()
     /// );
     /// ```
-	fn calc_best_paths(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError> {
-        // get unique and indexed currencies and providers
-        let currency_indexer = PositionIndexer::new(pairs_and_prices.iter().flat_map(|(ProviderPair { pair: Pair{source, target}, .. }, ..)| vec![source, target].into_iter()));
-        let provider_indexer = PositionIndexer::new(pairs_and_prices.iter().map(|(ProviderPair { provider, .. }, ..)| provider));
+	fn calc_best_paths_with(options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
 
-        // construct the graph for Floyd-Warshall lib — O(E), not O(currencies × E)
-        let graph = pairs_and_prices.iter().map(|(pp, cost)| {
-            Ok(algo::Edge {
-                pair:     algo::Pair { source: currency_indexer.by_val(&pp.pair.source), target: currency_indexer.by_val(&pp.pair.target) },
-                provider: provider_indexer.by_val(&pp.provider),
-                cost:     TryInto::<u128>::try_into(*cost).map_err(|_| CalculatorError::ConversionError)? as f64 / SCALE,
-            })
-        }).collect::<Result<Vec<algo::Edge>, CalculatorError>>()?;
-
-        // run Floyd-Warshall for all combinations of currencies in the graph
-        let res = algo::longest_paths_by_product(&graph)?;
-        let res_map = res.into_iter().map(|(algo::Pair{source, target}, algo::Path{total_cost, edges})| {
-            let pair = Pair{source: currency_indexer.by_ind(source), target: currency_indexer.by_ind(target)};
-            let total_cost_u128 = (total_cost * SCALE) as u128;
-            let path = PricePath{ total_cost: total_cost_u128.try_into().ok().unwrap(), steps: edges.into_iter().map(|algo::Edge{pair: algo::Pair{source, target, ..}, provider, cost}|
-                PathStep{pair: Pair{source: currency_indexer.by_ind(source), target: currency_indexer.by_ind(target)}, provider: provider_indexer.by_ind(provider), cost: ((cost * SCALE) as u128).try_into().ok().unwrap()}).collect()
-            };
-            (pair, path)
-        }).collect::<BTreeMap<_, _>>();
-        Ok(res_map)
+        // run Floyd-Warshall for all combinations of currencies in the graph, unless path length is limited
+        let res = match options.max_hops {
+            Some(max_hops) => best_paths_hop_limited(&graph.edges, options.accumulation, options.objective, max_hops),
+            None => algo::best_paths(&graph.edges, options.accumulation, options.objective),
+        }.map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph(res)
 	}
 }
+
+/// Floyd-Warshall relaxing via blocks of 64 currencies at a time, whose rows stay in cache, rather than one currency at a time.
+/// Pays off for a few hundred currencies and more, otherwise prefer `FloydWarshallCalculator`.
+pub struct BlockedFloydWarshallCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> AllPairsBestPathCalculator<C, A, P> for BlockedFloydWarshallCalculator {
+
+    /// Calculates best paths for all currency pairs, as per `FloydWarshallCalculator`. Paths are the same, including which
+    /// of equally good paths is chosen.
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::floyd_warshall::calculator::{BlockedFloydWarshallCalculator, FloydWarshallCalculator};
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ];
+    /// assert_eq!(
+    ///     FloydWarshallCalculator::calc_best_paths(in_graph).unwrap(),
+    ///     BlockedFloydWarshallCalculator::calc_best_paths(in_graph).unwrap(),
+    /// );
+    /// ```
+    fn calc_best_paths_with(options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        let res = match options.max_hops {
+            Some(max_hops) => best_paths_hop_limited(&graph.edges, options.accumulation, options.objective, max_hops),
+            None => algo::best_paths_blocked(&graph.edges, options.accumulation, options.objective),
+        }.map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph(res)
+    }
+}
+
+impl FloydWarshallCalculator {
+    /// Calculates best paths for all currency pairs, as per `calc_best_paths_with`, relaxing rows on the calling thread only,
+    /// even with the `parallel` feature, eg. to benchmark the two side by side.
+    pub fn calc_best_paths_sequential_with<C: Currency, A: Amount, P: Provider>(
+        options: &CalculatorOptions<C>,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        let res = match options.max_hops {
+            Some(max_hops) => best_paths_hop_limited(&graph.edges, options.accumulation, options.objective, max_hops),
+            None => algo::best_paths_sequential(&graph.edges, options.accumulation, options.objective),
+        }.map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph(res)
+    }
+
+    /// Calculates best paths for all currency pairs, maximising the product of exchange rates, building each only once
+    /// looked up. See `LazyPricePathGraph`.
+    pub fn calc_lazy_best_paths<C: Currency, A: Amount, P: Provider>(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<LazyPricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_lazy_best_paths_with(&CalculatorOptions::default(), pairs_and_prices)
+    }
+
+    /// Calculates best paths for all currency pairs, as per `options`, building each only once looked up.
+    /// Fails with `UnsupportedOptionError` on `max_hops`, as hop limited paths are not kept in a next-hop table.
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::floyd_warshall::calculator::FloydWarshallCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ];
+    /// let lazy = FloydWarshallCalculator::calc_lazy_best_paths_with(&CalculatorOptions::default(), in_graph).unwrap();
+    /// assert_eq!(999_701_550_000_u128, lazy.get(&Pair { source: "BNB", target: "ETH" }).unwrap().unwrap().total_cost);
+    /// assert_eq!(FloydWarshallCalculator::calc_best_paths(in_graph).unwrap(), lazy.to_price_path_graph().unwrap());
+    /// ```
+    pub fn calc_lazy_best_paths_with<C: Currency, A: Amount, P: Provider>(
+        options: &CalculatorOptions<C>,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<LazyPricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        if options.max_hops.is_some() {
+            return Err(CalculatorError::UnsupportedOptionError);
+        }
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+        let n = graph.currency_indexer.len();
+        let (transformed, tables) = algo::best_path_tables(&graph.edges, n, options.accumulation, options.objective)
+            .map_err(|err| graph.to_calculator_error(err))?;
+        Ok(LazyPricePathGraph::new(graph.into_labels(), transformed, tables))
+    }
+}
+
+impl<C: Currency, A: Amount, P: Provider> AllPairsWidestPathCalculator<C, A, P> for FloydWarshallCalculator {
+
+    /// Calculates widest paths for all currency pairs using Floyd-Warshall with max-min semantics, ie. the paths that
+    /// maximise the smallest capacity along the way. Of multiple providers per pair, the widest is kept, then the best rate.
+    /// Among equally wide paths, rates are not compared.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::floyd_warshall::calculator::FloydWarshallCalculator;
+    ///
+    /// let quotes = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "ETH" }, provider: "BINANCE" }, 153_000_000_000_u128, 10_u128),
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "BINANCE" }, 364_000_000_000_000_u128, 500_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "KRAKEN" }, 420_000_000_u128, 200_u128),
+    /// ];
+    /// let res = FloydWarshallCalculator::calc_widest_paths(quotes).unwrap();
+    /// let widest = res.get(&Pair { source: "BNB", target: "ETH" }).unwrap();
+    /// assert_eq!(200, widest.capacity);
+    /// assert_eq!(152_880_000_000_u128, widest.path.total_cost);
+    /// ```
+    fn calc_widest_paths(quotes: &[(ProviderPair<C, P>, A, A)]) -> Result<WidestPathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let (graph, capacities) = IndexedGraph::with_capacities(quotes)?;
+        graph.to_widest_path_graph(algo::widest_paths(&graph.edges, &capacities))
+    }
+}
@@ -6,7 +6,7 @@ use alloc::{
     vec, vec::Vec,
 };
 use crate::types::*;
-use crate::AllPairsBestPathCalculator;
+use crate::{AllPairsBestPathCalculator, CurrencyDecimals};
 use super::calculator::*;
 
 const MOCK_PROVIDER: &str = "MOCK_PROVIDER";
@@ -41,7 +41,7 @@ fn test_real_life_graph() {
         ("ETH".to_owned(),  "BTC".to_owned(),  MOCK_PROVIDER, 0.06626),
         ("ETH".to_owned(),  "BNB".to_owned(),  MOCK_PROVIDER, 6.548),
         ("BNB".to_owned(),  "ETH".to_owned(),  MOCK_PROVIDER, 0.1527),
-    ].into_iter().map(|(source, target, provider, cost)| (ProviderPair{pair: Pair{source: source.as_str().as_bytes().to_vec(), target: target.as_str().as_bytes().to_vec()}, provider}, (cost * SCALE) as u128)).collect::<Vec<_>>();
+    ].into_iter().map(|(source, target, provider, cost)| (ProviderPair{pair: Pair{source: source.as_bytes().to_vec(), target: target.as_bytes().to_vec()}, provider}, (cost * SCALE) as u128)).collect::<Vec<_>>();
     let res_out = FloydWarshallCalculator::calc_best_paths(&in_graph).unwrap().into_iter().collect::<Vec<(_, _)>>()
         .into_iter().map(|(p, pp)|(
             String::from_utf8(p.source).unwrap(),
@@ -63,9 +63,9 @@ fn test_real_life_graph() {
             ("BNB".to_owned(), "USDT".to_owned(), 364.19,            vec![("BNB".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 364.19)]),
             ("BTC".to_owned(), "BNB".to_owned(), 98.80932,           vec![("BTC".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 15.09), ("ETH".to_owned(), "BNB".to_owned(), MOCK_PROVIDER, 6.548)]),
             ("BTC".to_owned(), "BTC".to_owned(), 1.0,                vec![]),
-            ("BTC".to_owned(), "DOT".to_owned(), 2064.717563366999,  vec![("BTC".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 15.09), ("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99), ("USDT".to_owned(), "DOT".to_owned(), MOCK_PROVIDER, 0.05737)]),
+            ("BTC".to_owned(), "DOT".to_owned(), 2064.717563367,     vec![("BTC".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 15.09), ("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99), ("USDT".to_owned(), "DOT".to_owned(), MOCK_PROVIDER, 0.05737)]),
             ("BTC".to_owned(), "ETH".to_owned(), 15.09,              vec![("BTC".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 15.09)]),
-            ("BTC".to_owned(), "USDT".to_owned(), 35989.49909999999, vec![("BTC".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 15.09), ("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99)]),
+            ("BTC".to_owned(), "USDT".to_owned(), 35989.4991,        vec![("BTC".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 15.09), ("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99)]),
             ("DOT".to_owned(), "BNB".to_owned(), 0.04784535,         vec![("DOT".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 17.43), ("USDT".to_owned(), "BNB".to_owned(), MOCK_PROVIDER, 0.002745)]),
             ("DOT".to_owned(), "BTC".to_owned(), 0.000484139026,     vec![("DOT".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 17.43), ("USDT".to_owned(), "ETH".to_owned(), MOCK_PROVIDER, 0.0004192), ("ETH".to_owned(), "BTC".to_owned(), MOCK_PROVIDER, 0.06626)]),
             ("DOT".to_owned(), "DOT".to_owned(), 1.0,                vec![]),
@@ -73,7 +73,7 @@ fn test_real_life_graph() {
             ("DOT".to_owned(), "USDT".to_owned(), 17.43,             vec![("DOT".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 17.43)]),
             ("ETH".to_owned(), "BNB".to_owned(), 6.548,              vec![("ETH".to_owned(), "BNB".to_owned(), MOCK_PROVIDER, 6.548)]),
             ("ETH".to_owned(), "BTC".to_owned(), 0.06626,            vec![("ETH".to_owned(), "BTC".to_owned(), MOCK_PROVIDER, 0.06626)]),
-            ("ETH".to_owned(), "DOT".to_owned(), 136.826876299999,   vec![("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99), ("USDT".to_owned(), "DOT".to_owned(), MOCK_PROVIDER, 0.05737)]),
+            ("ETH".to_owned(), "DOT".to_owned(), 136.8268763,        vec![("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99), ("USDT".to_owned(), "DOT".to_owned(), MOCK_PROVIDER, 0.05737)]),
             ("ETH".to_owned(), "ETH".to_owned(), 1.0,                vec![]),
             ("ETH".to_owned(), "USDT".to_owned(), 2384.99,           vec![("ETH".to_owned(), "USDT".to_owned(), MOCK_PROVIDER, 2384.99)]),
             ("USDT".to_owned(), "BNB".to_owned(), 0.002745,          vec![("USDT".to_owned(), "BNB".to_owned(), MOCK_PROVIDER, 0.002745)]),
@@ -161,4 +161,271 @@ fn test_simple_example_graph() {
         ],
         as_nodes
     );
-}
\ No newline at end of file
+}
+
+/// Arbitrage BTC → USDT → BTC at 36000 * 0.00003 = 1.08 is reported with its steps and profit ratio.
+#[test]
+fn test_negative_cycle_reported() {
+    let in_graph = &[
+        (ProviderPair { pair: Pair { source: "BTC".to_owned(), target: "USDT".to_owned() }, provider: "BINANCE".to_owned() }, 36_000_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "USDT".to_owned(), target: "BTC".to_owned() }, provider: "KRAKEN".to_owned() }, 30_000_000_u128),
+    ];
+    assert_eq!(
+        Err(CalculatorError::NegativeCyclesError(PricePath { total_cost: 1_080_000_000_000_u128, steps: vec![
+            PathStep { pair: Pair { source: "USDT".to_owned(), target: "BTC".to_owned() }, provider: "KRAKEN".to_owned(), cost: 30_000_000_u128 },
+            PathStep { pair: Pair { source: "BTC".to_owned(), target: "USDT".to_owned() }, provider: "BINANCE".to_owned(), cost: 36_000_000_000_000_000_u128 },
+        ] })),
+        FloydWarshallCalculator::calc_best_paths(in_graph)
+    );
+}
+
+fn latency_graph() -> Vec<(ProviderPair<&'static str, &'static str>, u128)> {
+    vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P1" }, 5_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P1" }, 3_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "P1" }, 10_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "P2" }, 9_000_000_000_000_u128),
+    ]
+}
+
+/// Additive costs, eg. latency: A → B → C at 5 + 3 beats direct A → C at 9, self references cost nothing.
+#[test]
+fn test_sum_minimise() {
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() };
+    let res = FloydWarshallCalculator::calc_best_paths_with(&options, &latency_graph()).unwrap();
+    assert_eq!(
+        &PricePath { total_cost: 8_000_000_000_000_u128, steps: vec![
+            PathStep { pair: Pair { source: "A", target: "B" }, provider: "P1", cost: 5_000_000_000_000_u128 },
+            PathStep { pair: Pair { source: "B", target: "C" }, provider: "P1", cost: 3_000_000_000_000_u128 },
+        ] },
+        res.get(&Pair { source: "A", target: "C" }).unwrap()
+    );
+    assert_eq!(&PricePath { total_cost: 0_u128, steps: vec![] }, res.get(&Pair { source: "A", target: "A" }).unwrap());
+}
+
+/// Maximising the sum picks the longest route, direct A → C at 10 (provider P1) beats 5 + 3.
+#[test]
+fn test_sum_maximise() {
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Maximise, ..Default::default() };
+    let res = FloydWarshallCalculator::calc_best_paths_with(&options, &latency_graph()).unwrap();
+    assert_eq!(
+        &PricePath { total_cost: 10_000_000_000_000_u128, steps: vec![
+            PathStep { pair: Pair { source: "A", target: "C" }, provider: "P1", cost: 10_000_000_000_000_u128 },
+        ] },
+        res.get(&Pair { source: "A", target: "C" }).unwrap()
+    );
+}
+
+/// Maximising a sum over a round trip never settles, reported as a cycle.
+#[test]
+fn test_sum_maximise_cycle() {
+    let mut graph = latency_graph();
+    graph.push((ProviderPair { pair: Pair { source: "C", target: "A" }, provider: "P1" }, 1_000_000_000_000_u128));
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Maximise, ..Default::default() };
+    match FloydWarshallCalculator::calc_best_paths_with(&options, &graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            // first cycle found is the direct round trip C → A → C, at 1 + 10
+            assert_eq!(11_000_000_000_000_u128, cycle.total_cost);
+            assert_eq!(vec!["C", "A"], cycle.steps.iter().map(|s| s.pair.source).collect::<Vec<_>>());
+        }
+        other => panic!("expected cycle, got {other:?}"),
+    }
+}
+
+/// Slippage multipliers, smallest product wins: A → B → C at 1.01 * 1.02 = 1.0302 beats direct 1.05.
+#[test]
+fn test_product_minimise() {
+    let in_graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P" }, 1_010_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P" }, 1_020_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "P" }, 1_050_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "Q" }, 0_u128), // missing quote, ignored
+    ];
+    let options = CalculatorOptions { accumulation: Accumulation::Product, objective: Objective::Minimise, ..Default::default() };
+    let res = FloydWarshallCalculator::calc_best_paths_with(&options, &in_graph).unwrap();
+    let path = res.get(&Pair { source: "A", target: "C" }).unwrap();
+    assert_eq!(vec!["P", "P"], path.steps.iter().map(|s| s.provider).collect::<Vec<_>>());
+    assert_eq!(1_030_200_000_000_u128, path.total_cost);
+    assert_eq!(&PricePath { total_cost: 1_000_000_000_000_u128, steps: vec![] }, res.get(&Pair { source: "A", target: "A" }).unwrap());
+}
+
+/// When minimising, a cycle with product below 1 is unbounded.
+#[test]
+fn test_product_minimise_cycle() {
+    let in_graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P" }, 1_010_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "B", target: "A" }, provider: "P" }, 900_000_000_000_u128),
+    ];
+    let options = CalculatorOptions { accumulation: Accumulation::Product, objective: Objective::Minimise, ..Default::default() };
+    match FloydWarshallCalculator::calc_best_paths_with(&options, &in_graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            assert_eq!(2, cycle.steps.len());
+            assert_eq!(909_000_000_000_u128, cycle.total_cost);
+        }
+        other => panic!("expected cycle, got {other:?}"),
+    }
+}
+
+/// Limited to a single hop, the direct A → C quote wins over the cheaper A → B → C.
+#[test]
+fn test_max_hops() {
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, max_hops: Some(1), ..Default::default() };
+    let res = FloydWarshallCalculator::calc_best_paths_with(&options, &latency_graph()).unwrap();
+    assert_eq!(
+        &PricePath { total_cost: 9_000_000_000_000_u128, steps: vec![
+            PathStep { pair: Pair { source: "A", target: "C" }, provider: "P2", cost: 9_000_000_000_000_u128 },
+        ] },
+        res.get(&Pair { source: "A", target: "C" }).unwrap()
+    );
+    assert_eq!(&PricePath { total_cost: 0_u128, steps: vec![] }, res.get(&Pair { source: "C", target: "C" }).unwrap());
+
+    // non binding limit, as per unrestricted
+    let unlimited = CalculatorOptions { max_hops: None, ..options.clone() };
+    assert_eq!(
+        FloydWarshallCalculator::calc_best_paths_with(&unlimited, &latency_graph()).unwrap(),
+        FloydWarshallCalculator::calc_best_paths_with(&CalculatorOptions { max_hops: Some(2), ..options }, &latency_graph()).unwrap(),
+    );
+}
+
+/// With a hop limit, paths are bounded, so a cycle is no longer an error.
+#[test]
+fn test_max_hops_cycle() {
+    let mut graph = latency_graph();
+    graph.push((ProviderPair { pair: Pair { source: "C", target: "A" }, provider: "P1" }, 1_000_000_000_000_u128));
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Maximise, max_hops: Some(3), ..Default::default() };
+    let res = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+    // A → C → A → C at 10 + 1 + 10
+    assert_eq!(21_000_000_000_000_u128, res.get(&Pair { source: "A", target: "C" }).unwrap().total_cost);
+    assert!(res.values().all(|p| p.steps.len() <= 3));
+}
+
+/// The blocked calculator returns the very same result, cycles included, for every accumulation, objective and hop limit.
+#[test]
+fn test_blocked_parity() {
+    let mut cyclic = latency_graph();
+    cyclic.push((ProviderPair { pair: Pair { source: "C", target: "A" }, provider: "P1" }, 1_000_000_000_000_u128));
+    for graph in [latency_graph(), cyclic] {
+        for options in [
+            CalculatorOptions::default(),
+            CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+            CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Maximise, ..Default::default() },
+            CalculatorOptions { accumulation: Accumulation::Product, objective: Objective::Minimise, ..Default::default() },
+            CalculatorOptions { max_hops: Some(1), ..Default::default() },
+        ] {
+            assert_eq!(
+                FloydWarshallCalculator::calc_best_paths_with(&options, &graph),
+                BlockedFloydWarshallCalculator::calc_best_paths_with(&options, &graph),
+            );
+        }
+    }
+}
+
+/// Steps carry the amounts supplied, even beyond the precision of f64, and total_cost is their exact product.
+#[test]
+fn test_exact_amounts() {
+    // 2^54 + 1 and 2^54 + 2 are the same f64
+    let graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P1" }, (1_u128 << 54) + 1),
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P2" }, (1_u128 << 54) + 2),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P1" }, 3_000_000_000_001_u128),
+    ];
+    let res = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+    let path = &res[&Pair { source: "A", target: "C" }];
+    let quoted = graph.iter().find(|(pp, _)| pp.pair == path.steps[0].pair && pp.provider == path.steps[0].provider).unwrap().1;
+    assert_eq!(quoted, path.steps[0].cost);
+    assert_eq!(3_000_000_000_001_u128, path.steps[1].cost);
+    assert_eq!(quoted * 3_000_000_000_001 / 1_000_000_000_000, path.total_cost);
+}
+
+/// total_cost is rounded once, as per `rounding`.
+#[test]
+fn test_rounding() {
+    // 1.5 * 0.000000000001
+    let graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P1" }, 1_500_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P1" }, 1_u128),
+    ];
+    for (rounding, expected) in [(Rounding::Down, 1), (Rounding::HalfUp, 2), (Rounding::Up, 2)] {
+        let res = FloydWarshallCalculator::calc_best_paths_with(&CalculatorOptions { rounding, ..Default::default() }, &graph).unwrap();
+        assert_eq!(expected, res[&Pair { source: "A", target: "C" }].total_cost);
+    }
+}
+
+/// Amounts in any scale give the same paths, with costs and self references in that scale.
+#[test]
+fn test_scale() {
+    let graph = |scale: u128| vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P1" }, 25 * scale / 10),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P1" }, 4 * scale / 10),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "P1" }, 9 * scale / 10),
+    ];
+    for scale in [100_000_000, 1_000_000_000_000, 1_000_000_000_000_000_000] {
+        let res = FloydWarshallCalculator::calc_best_paths_with(&CalculatorOptions { scale, ..Default::default() }, &graph(scale)).unwrap();
+        assert_eq!(scale, res[&Pair { source: "A", target: "A" }].total_cost);
+        let path = &res[&Pair { source: "A", target: "C" }];
+        assert_eq!(scale, path.total_cost);
+        assert_eq!(vec![25 * scale / 10, 4 * scale / 10], path.steps.iter().map(|step| step.cost).collect::<Vec<_>>());
+    }
+    assert_eq!(
+        Err(CalculatorError::ConversionError),
+        FloydWarshallCalculator::calc_best_paths_with(&CalculatorOptions { scale: 0, ..Default::default() }, &graph(1_000)),
+    );
+}
+
+/// Quotes as ratios of base units, normalised by currency decimals, with total_cost in base units of the target.
+#[test]
+fn test_decimals() {
+    let decimals = CurrencyDecimals::from([("BTC", 8), ("ETH", 18), ("USDT", 6)]);
+    let graph = vec![
+        // 35989.5 USDT per BTC, ie. 359.895 micro USDT per sat
+        (ProviderPair { pair: Pair { source: "BTC", target: "USDT" }, provider: "P1" }, 359_895_000_000_000_u128),
+        // 0.0004194 ETH per USDT, ie. 419_400_000 wei per micro USDT
+        (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "P1" }, 419_400_000_000_000_000_000_u128),
+        // 15.09 ETH per BTC, ie. 150_900_000_000 wei per sat
+        (ProviderPair { pair: Pair { source: "BTC", target: "ETH" }, provider: "P1" }, 150_900_000_000_000_000_000_000_u128),
+    ];
+    let options = CalculatorOptions { decimals, ..Default::default() };
+    let res = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+    // 35989.5 * 0.0004194 = 15.0939963 ETH beats 15.09 ETH, in wei
+    let path = &res[&Pair { source: "BTC", target: "ETH" }];
+    assert_eq!(2, path.steps.len());
+    assert_eq!(15_093_996_300_000_000_000_u128, path.total_cost);
+    assert_eq!(419_400_000_000_000_u128, res[&Pair { source: "USDT", target: "ETH" }].total_cost);
+    for (currency, unit) in [("BTC", 100_000_000), ("ETH", 1_000_000_000_000_000_000), ("USDT", 1_000_000)] {
+        assert_eq!(unit, res[&Pair { source: currency, target: currency }].total_cost);
+    }
+
+    // steps as supplied
+    assert_eq!(graph[..2].iter().map(|(_, amount)| *amount).collect::<Vec<_>>(), path.steps.iter().map(|step| step.cost).collect::<Vec<_>>());
+
+    // 10^39 overflows u128, and a currency missing is not guessed
+    for decimals in [CurrencyDecimals::from([("BTC", 39), ("ETH", 18), ("USDT", 6)]), CurrencyDecimals::from([("BTC", 8), ("ETH", 18)])] {
+        assert_eq!(
+            Err(CalculatorError::ConversionError),
+            FloydWarshallCalculator::calc_best_paths_with(&CalculatorOptions { decimals, ..Default::default() }, &graph),
+        );
+    }
+}
+
+/// Narrow amounts that cannot take a result fail, naming its pair, rather than panic.
+#[test]
+fn test_overflow_error() {
+    // 2^32 * 2^32 overflows u64, in any scale
+    let graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P1" }, 1_u64 << 32),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P1" }, 1_u64 << 32),
+    ];
+    let options = CalculatorOptions { scale: 1, ..Default::default() };
+    let expected = CalculatorError::OverflowError(Pair { source: "A", target: "C" });
+    assert_eq!(Err(expected.clone()), FloydWarshallCalculator::calc_best_paths_with(&options, &graph));
+    let lazy = FloydWarshallCalculator::calc_lazy_best_paths_with(&options, &graph).unwrap();
+    assert_eq!(Err(expected), lazy.get(&Pair { source: "A", target: "C" }));
+    assert_eq!(Ok(Some(1_u64 << 32)), lazy.get(&Pair { source: "A", target: "B" }).map(|path| path.map(|path| path.total_cost)));
+
+    // self references cost a scale that does not fit
+    let options = CalculatorOptions { scale: u64::MAX as u128 + 1, ..Default::default() };
+    assert_eq!(
+        Err(CalculatorError::OverflowError(Pair { source: "A", target: "A" })),
+        FloydWarshallCalculator::calc_best_paths_with(&options, &graph),
+    );
+}
@@ -0,0 +1,268 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::{BTreeMap, BTreeSet}, vec, vec::Vec};
+use core::marker::PhantomData;
+use super::algo::{self, Tables};
+use super::super::{IndexedGraph, Labels, currency_unit};
+use super::super::bellman_ford::algo::bellman_ford;
+use super::super::algo_types::{Edge, Pair as IndexedPair, Path, PathCalculationError, TransformedGraph};
+use crate::types::*;
+use crate::*;
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use super::super::SCALE;
+
+/// All pairs best paths, as per `FloydWarshallCalculator`, retained between quote updates.
+///
+/// Keeps the Floyd-Warshall cost and next-hop tables, so that a quote improving on the best rate of its pair updates best
+/// paths in O(V²), rather than O(V³) for a full recalculation. Worse rates, and removed quotes or providers, only recalculate
+/// the rows of sources whose best paths were affected. Paths are built on lookup.
+pub struct IncrementalFloydWarshall<C: Currency, A: Amount, P: Provider> {
+    options: CalculatorOptions<C>,
+    /// currencies, providers, and the latest amount per pair and provider, as supplied
+    labels: Labels<C, P>,
+    currency_ids: BTreeMap<C, usize>,
+    provider_ids: BTreeMap<P, usize>,
+    /// latest cost per pair and provider, in scale of 1
+    quotes: BTreeMap<(IndexedPair, usize), f64>,
+    tables: Tables,
+    _amount: PhantomData<A>,
+}
+
+impl<C: Currency, A: Amount, P: Provider> IncrementalFloydWarshall<C, A, P> {
+    /// Calculates best paths, maximising the product of exchange rates.
+    pub fn new(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<Self, CalculatorError<C, A, P>> {
+        Self::with_options(&CalculatorOptions::default(), pairs_and_prices)
+    }
+
+    /// Calculates best paths, as per `options`. Fails with `UnsupportedOptionError` on `max_hops`, as hop limited paths are
+    /// not kept in the tables.
+    pub fn with_options(options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<Self, CalculatorError<C, A, P>> {
+        if options.max_hops.is_some() {
+            return Err(CalculatorError::UnsupportedOptionError);
+        }
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        // of repeated quotes for the same pair and provider, keep the best, as would the calculator
+        let transformed = TransformedGraph::per_provider(&graph.edges, options.accumulation, options.objective);
+        let quotes = transformed.edges.iter().map(|e| ((e.pair, e.provider), transformed.original(e))).collect();
+
+        let tables = recalculate(options, &quotes, graph.currency_indexer.len()).map_err(|err| graph.to_calculator_error(err))?;
+        let labels = graph.into_labels();
+
+        Ok(Self {
+            options: options.clone(),
+            currency_ids: labels.currencies.iter().cloned().enumerate().map(|(ind, c)| (c, ind)).collect(),
+            provider_ids: labels.providers.iter().cloned().enumerate().map(|(ind, p)| (p, ind)).collect(),
+            labels,
+            quotes,
+            tables,
+            _amount: PhantomData,
+        })
+    }
+
+    /// Sets the price of a provider pair, adding currencies and providers as needed, and returns the pairs whose best path
+    /// changed. A better rate for the pair is applied in O(V²), a worse one recalculates the rows of sources whose best
+    /// paths took the pair, and a new currency recalculates all pairs.
+    /// Fails if the update closes a cycle, eg. arbitrage, in which case it is not applied, or with `ConversionError` for a
+    /// new currency missing from `options.decimals`, if any.
+    pub fn update(&mut self, provider_pair: &ProviderPair<C, P>, price: A) -> Result<BTreeSet<Pair<C>>, CalculatorError<C, A, P>> {
+        let amount = TryInto::<u128>::try_into(price).map_err(|_| CalculatorError::ConversionError)?;
+        let (currency_count, provider_count) = (self.labels.currencies.len(), self.labels.providers.len());
+        let known_currencies = self.currency_ids.contains_key(&provider_pair.pair.source) && self.currency_ids.contains_key(&provider_pair.pair.target);
+        let pair = match self.indexed_pair(&provider_pair.pair) {
+            Ok(pair) => pair,
+            Err(err) => {
+                self.unregister(currency_count, provider_count);
+                return Err(err);
+            }
+        };
+        let cost = self.labels.amounts.to_rate(pair, amount);
+        let provider = self.provider_id(&provider_pair.provider);
+
+        let previous_best = self.best_edge(pair);
+        let previous_quote = self.quotes.insert((pair, provider), cost);
+        let previous_amounts = self.labels.amounts.remove(pair, provider);
+        self.labels.amounts.insert(pair, provider, amount, true);
+        let best = self.best_edge(pair);
+
+        let changed = match (previous_best, best) {
+            _ if !known_currencies => self.recalculate(),
+            (previous_best, best) if previous_best == best => Ok(vec![]),
+            (previous_best, Some(best)) if previous_best.map_or(true, |prev| best.cost < prev.cost) => self.tables.improve(best),
+            _ => self.worsen(&[pair]),
+        };
+        match changed {
+            Ok(changed) => Ok(changed.into_iter().map(|pair| self.to_pair(pair)).collect()),
+            Err(err) => {
+                let err = self.to_calculator_error(err);
+                match previous_quote {
+                    Some(previous_quote) => self.quotes.insert((pair, provider), previous_quote),
+                    None => self.quotes.remove(&(pair, provider)),
+                };
+                self.labels.amounts.restore(pair, provider, previous_amounts);
+                self.unregister(currency_count, provider_count);
+                Err(err)
+            }
+        }
+    }
+
+    /// Removes the quote of a provider pair, eg. once delisted, and returns the pairs whose best path changed.
+    /// Only the rows of sources whose best paths took the pair are recalculated.
+    pub fn remove(&mut self, provider_pair: &ProviderPair<C, P>) -> Result<BTreeSet<Pair<C>>, CalculatorError<C, A, P>> {
+        let (Some(&source), Some(&target), Some(&provider)) = (
+            self.currency_ids.get(&provider_pair.pair.source),
+            self.currency_ids.get(&provider_pair.pair.target),
+            self.provider_ids.get(&provider_pair.provider),
+        ) else {
+            return Ok(BTreeSet::new());
+        };
+        self.remove_quotes(&[(IndexedPair { source, target }, provider)])
+    }
+
+    /// Removes all quotes of a provider, eg. once offline, and returns the pairs whose best path changed.
+    /// Only the rows of sources whose best paths took any of its quotes are recalculated.
+    pub fn remove_provider(&mut self, provider: &P) -> Result<BTreeSet<Pair<C>>, CalculatorError<C, A, P>> {
+        let Some(&provider) = self.provider_ids.get(provider) else {
+            return Ok(BTreeSet::new());
+        };
+        let keys = self.quotes.keys().filter(|(_, quote_provider)| *quote_provider == provider).copied().collect::<Vec<_>>();
+        self.remove_quotes(&keys)
+    }
+
+    /// Best path for a pair, if any. Fails with `OverflowError` if it does not fit `A`.
+    #[allow(clippy::type_complexity)]
+    pub fn best_path(&self, pair: &Pair<C>) -> Result<Option<PricePath<C, A, P>>, CalculatorError<C, A, P>> {
+        let (Some(&source), Some(&target)) = (self.currency_ids.get(&pair.source), self.currency_ids.get(&pair.target)) else {
+            return Ok(None);
+        };
+        self.tables.path(source, target).map(|path| self.to_price_path(source, path)).transpose()
+    }
+
+    /// Best paths for all pairs, as per `FloydWarshallCalculator::calc_best_paths_with`.
+    pub fn best_paths(&self) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        self.tables.paths().into_iter().map(|(pair, path)| Ok((self.to_pair(pair), self.to_price_path(pair.source, path)?))).collect()
+    }
+
+    fn indexed_pair(&mut self, pair: &Pair<C>) -> Result<IndexedPair, CalculatorError<C, A, P>> {
+        Ok(IndexedPair { source: self.currency_id(&pair.source)?, target: self.currency_id(&pair.target)? })
+    }
+
+    fn currency_id(&mut self, currency: &C) -> Result<usize, CalculatorError<C, A, P>> {
+        match self.currency_ids.get(currency) {
+            Some(&ind) => Ok(ind),
+            None => {
+                if !self.options.decimals.is_empty() {
+                    self.labels.amounts.units.push(currency_unit(&self.options, currency)?);
+                }
+                self.labels.currencies.push(currency.clone());
+                self.currency_ids.insert(currency.clone(), self.labels.currencies.len() - 1);
+                Ok(self.labels.currencies.len() - 1)
+            }
+        }
+    }
+
+    fn provider_id(&mut self, provider: &P) -> usize {
+        match self.provider_ids.get(provider) {
+            Some(&ind) => ind,
+            None => {
+                self.labels.providers.push(provider.clone());
+                self.provider_ids.insert(provider.clone(), self.labels.providers.len() - 1);
+                self.labels.providers.len() - 1
+            }
+        }
+    }
+
+    /// Unregisters currencies and providers added by a rejected update, which the tables do not cover.
+    fn unregister(&mut self, currency_count: usize, provider_count: usize) {
+        for currency in self.labels.currencies.drain(currency_count..) {
+            self.currency_ids.remove(&currency);
+        }
+        self.labels.amounts.units.truncate(currency_count);
+        for provider in self.labels.providers.drain(provider_count..) {
+            self.provider_ids.remove(&provider);
+        }
+    }
+
+    /// Best transformed edge for a pair, as chosen by the calculator.
+    fn best_edge(&self, pair: IndexedPair) -> Option<Edge> {
+        let edges = self.quotes.range((pair, 0)..=(pair, usize::MAX))
+            .map(|(&(pair, provider), &cost)| Edge { pair, provider, cost })
+            .collect::<Vec<_>>();
+        TransformedGraph::new(&edges, self.options.accumulation, self.options.objective).edges.first().copied()
+    }
+
+    fn remove_quotes(&mut self, keys: &[(IndexedPair, usize)]) -> Result<BTreeSet<Pair<C>>, CalculatorError<C, A, P>> {
+        let previous_bests = keys.iter().map(|&(pair, _)| (pair, self.best_edge(pair))).collect::<Vec<_>>();
+        for &(pair, provider) in keys {
+            self.quotes.remove(&(pair, provider));
+            self.labels.amounts.remove(pair, provider);
+        }
+        let worsened = previous_bests.into_iter().filter(|&(pair, previous_best)| previous_best != self.best_edge(pair)).map(|(pair, _)| pair).collect::<Vec<_>>();
+        let changed = self.worsen(&worsened).map_err(|err| self.to_calculator_error(err))?;
+        Ok(changed.into_iter().map(|pair| self.to_pair(pair)).collect())
+    }
+
+    /// Applies worse best quotes for `pairs`, or none, recalculating rows of sources whose best paths took them by Bellman-Ford.
+    /// Unaffected rows hold, as worse quotes cannot improve on any path. Only the recalculated rows are diffed, along with
+    /// those of the pairs' currencies, which may cease to exist.
+    fn worsen(&mut self, pairs: &[IndexedPair]) -> Result<Vec<IndexedPair>, PathCalculationError> {
+        if pairs.is_empty() {
+            return Ok(vec![]);
+        }
+        let rows = self.tables.rows_using(pairs);
+        let mut touched = rows.iter().copied().chain(pairs.iter().flat_map(|pair| [pair.source, pair.target])).collect::<Vec<_>>();
+        touched.sort_unstable();
+        touched.dedup();
+        let previous = self.tables.rows(&touched);
+
+        let transformed = TransformedGraph::new(&quote_edges(&self.quotes), self.options.accumulation, self.options.objective);
+        let n = self.tables.n;
+        for pair in pairs {
+            self.tables.edge_for[pair.source * n + pair.target] = transformed.edges.iter().find(|e| e.pair == *pair).copied();
+            for vertex in [pair.source, pair.target] {
+                self.tables.vertex_exists[vertex] = transformed.edges.iter().any(|e| e.pair.source == vertex || e.pair.target == vertex);
+            }
+        }
+        for source in rows {
+            let paths = if self.tables.vertex_exists[source] { bellman_ford(&transformed.edges, source)? } else { BTreeMap::new() };
+            self.tables.set_row(source, &paths);
+        }
+        Ok(self.tables.changed_rows_since(&previous, pairs))
+    }
+
+    /// Recalculates all pairs, returning those whose best path changed.
+    fn recalculate(&mut self) -> Result<Vec<IndexedPair>, PathCalculationError> {
+        let tables = recalculate(&self.options, &self.quotes, self.labels.currencies.len())?;
+        let previous = core::mem::replace(&mut self.tables, tables);
+        Ok(self.tables.changed_since(&previous))
+    }
+
+    fn to_pair(&self, IndexedPair { source, target }: IndexedPair) -> Pair<C> {
+        Pair { source: self.labels.currencies[source].clone(), target: self.labels.currencies[target].clone() }
+    }
+
+    /// Maps a transformed path back to original costs, then unwraps it.
+    fn to_price_path(&self, source: usize, path: Path) -> Result<PricePath<C, A, P>, CalculatorError<C, A, P>> {
+        self.labels.to_price_path(source, self.original(path))
+    }
+
+    fn to_calculator_error(&self, PathCalculationError::NegativeCyclesError(cycle): PathCalculationError) -> CalculatorError<C, A, P> {
+        self.labels.to_calculator_error(PathCalculationError::NegativeCyclesError(self.original(cycle)))
+    }
+
+    /// A transformed path, with the original costs of its quotes.
+    fn original(&self, path: Path) -> Path {
+        Path { edges: path.edges.iter().map(|e| Edge { cost: self.quotes[&(e.pair, e.provider)], ..*e }).collect(), ..path }
+    }
+}
+
+fn quote_edges(quotes: &BTreeMap<(IndexedPair, usize), f64>) -> Vec<Edge> {
+    quotes.iter().map(|(&(pair, provider), &cost)| Edge { pair, provider, cost }).collect()
+}
+
+/// Full Floyd-Warshall over the best quote per pair, for currencies 0..n.
+fn recalculate<C>(options: &CalculatorOptions<C>, quotes: &BTreeMap<(IndexedPair, usize), f64>, n: usize) -> Result<Tables, PathCalculationError> {
+    let transformed = TransformedGraph::new(&quote_edges(quotes), options.accumulation, options.objective);
+    algo::floyd_warshall_tables(&transformed.edges, n)
+}
@@ -0,0 +1,242 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, collections::BTreeSet, format, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+use crate::types::*;
+use crate::{AllPairsBestPathCalculator, CurrencyDecimals, PricePathGraph};
+use super::calculator::{FloydWarshallCalculator, SCALE};
+use super::incremental::IncrementalFloydWarshall;
+use crate::best_path_calculator::fixtures::{pair, quote, real_life_graph};
+
+/// Sets a quote in the input graph, as the full calculation would see it.
+fn apply(graph: &mut Vec<(ProviderPair<String, String>, u128)>, (provider_pair, price): (ProviderPair<String, String>, u128)) {
+    graph.retain(|(pp, _)| pp != &provider_pair);
+    graph.push((provider_pair, price));
+}
+
+/// Pairs added, removed, or with a different best path.
+fn changed_pairs(before: &PricePathGraph<String, u128, String>, after: &PricePathGraph<String, u128, String>) -> BTreeSet<Pair<String>> {
+    before.keys().chain(after.keys()).filter(|pair| before.get(*pair) != after.get(*pair)).cloned().collect()
+}
+
+#[test]
+fn test_initial_parity() {
+    let graph = real_life_graph();
+    let incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+    assert_eq!(FloydWarshallCalculator::calc_best_paths(&graph).unwrap(), incremental.best_paths().unwrap());
+    assert_eq!(
+        FloydWarshallCalculator::calc_best_paths(&graph).unwrap().remove(&pair("BNB", "ETH")),
+        incremental.best_path(&pair("BNB", "ETH")).unwrap()
+    );
+    assert_eq!(None, incremental.best_path(&pair("BNB", "XYZ")).unwrap());
+}
+
+/// Improving quotes, via a new provider or an existing one, give the same result as a full calculation, and report exactly
+/// the pairs whose best path changed.
+#[test]
+fn test_improving_updates() {
+    let mut graph = real_life_graph();
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+
+    for update in [
+        quote("ETH", "DOT", "Q", 136.84),    // beats ETH → USDT → DOT at 136.83
+        quote("ETH", "DOT", "Q", 136.85),
+        quote("BNB", "ETH", "Q", 0.15271),   // just short of arbitrage with ETH → BNB
+    ] {
+        let before = incremental.best_paths().unwrap();
+        let changed = incremental.update(&update.0, update.1).unwrap();
+        apply(&mut graph, update);
+        let after = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+        assert_eq!(after, incremental.best_paths().unwrap());
+
+        assert!(!changed.is_empty());
+        assert_eq!(changed_pairs(&before, &after), changed);
+    }
+}
+
+/// A quote that is no better than the pair's best changes nothing.
+#[test]
+fn test_non_improving_update() {
+    let graph = real_life_graph();
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+    let (provider_pair, price) = quote("BNB", "ETH", "Q", 0.1500);
+    assert!(incremental.update(&provider_pair, price).unwrap().is_empty());
+    assert_eq!(FloydWarshallCalculator::calc_best_paths(&graph).unwrap(), incremental.best_paths().unwrap());
+}
+
+/// Worse rates, including a zero rate dropping the quote, and new currencies give the same result as a full calculation, and
+/// report exactly the pairs whose best path changed.
+#[test]
+fn test_worsening_updates() {
+    let mut graph = real_life_graph();
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+
+    for update in [quote("USDT", "ETH", "P", 0.0004), quote("ETH", "SOL", "P", 60.0), quote("BNB", "ETH", "P", 0.0), quote("ETH", "USDT", "P", 2000.0)] {
+        let before = incremental.best_paths().unwrap();
+        let changed = incremental.update(&update.0, update.1).unwrap();
+        apply(&mut graph, update);
+        let after = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+        assert_eq!(after, incremental.best_paths().unwrap());
+        assert!(!changed.is_empty());
+        assert_eq!(changed_pairs(&before, &after), changed);
+    }
+}
+
+/// Over more currencies, with paths sharing quotes, worse rates and removed quotes still report exactly the pairs whose
+/// best path changed.
+#[test]
+fn test_worsening_updates_large_graph() {
+    let n = 40;
+    let currency = |i: usize| format!("C{i}");
+    let mut graph = (0..n)
+        .flat_map(|i| [(i + 1) % n, (i * 7 + 3) % n].map(|j| (i, j)))
+        .filter(|&(i, j)| i != j)
+        .map(|(i, j)| quote(&currency(i), &currency(j), "P", 0.5 + ((i * 37 + j) % 50) as f64 / 100.0))
+        .collect::<Vec<_>>();
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+
+    for i in [0, 5, 13, 27, 39] {
+        let update = quote(&currency(i), &currency((i + 1) % n), "P", 0.1);
+        let before = incremental.best_paths().unwrap();
+        let changed = incremental.update(&update.0, update.1).unwrap();
+        apply(&mut graph, update);
+        let after = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+        assert_eq!(after, incremental.best_paths().unwrap());
+        assert_eq!(changed_pairs(&before, &after), changed);
+
+        let (provider_pair, _) = quote(&currency(i), &currency((i * 7 + 3) % n), "P", 0.0);
+        let before = incremental.best_paths().unwrap();
+        let changed = incremental.remove(&provider_pair).unwrap();
+        graph.retain(|(pp, _)| pp != &provider_pair);
+        let after = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+        assert_eq!(after, incremental.best_paths().unwrap());
+        assert_eq!(changed_pairs(&before, &after), changed);
+    }
+}
+
+/// Removed quotes give the same result as a full calculation without them, including once a currency has no quotes left.
+#[test]
+fn test_remove() {
+    let mut graph = real_life_graph();
+    graph.push(quote("BNB", "ETH", "Q", 0.1520));
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+
+    for (source, target, provider) in [("BNB", "ETH", "P"), ("USDT", "DOT", "P"), ("DOT", "USDT", "P"), ("ETH", "BTC", "P")] {
+        let (provider_pair, _) = quote(source, target, provider, 0.0);
+        let before = incremental.best_paths().unwrap();
+        let changed = incremental.remove(&provider_pair).unwrap();
+        graph.retain(|(pp, _)| pp != &provider_pair);
+        let after = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+        assert_eq!(after, incremental.best_paths().unwrap());
+        assert!(!changed.is_empty());
+        assert_eq!(changed_pairs(&before, &after), changed);
+    }
+    assert_eq!(None, incremental.best_path(&pair("DOT", "DOT")).unwrap());
+
+    // unknown, or already removed, quotes change nothing
+    assert!(incremental.remove(&quote("USDT", "DOT", "P", 0.0).0).unwrap().is_empty());
+    assert!(incremental.remove(&quote("USDT", "XYZ", "P", 0.0).0).unwrap().is_empty());
+
+    // a removed currency may be quoted again
+    let update = quote("DOT", "USDT", "P", 17.43);
+    incremental.update(&update.0, update.1).unwrap();
+    apply(&mut graph, update);
+    assert_eq!(FloydWarshallCalculator::calc_best_paths(&graph).unwrap(), incremental.best_paths().unwrap());
+}
+
+/// Removing a provider drops all its quotes, leaving best paths via the remaining providers.
+#[test]
+fn test_remove_provider() {
+    let mut graph = real_life_graph();
+    graph.extend([quote("BNB", "ETH", "Q", 0.15271), quote("ETH", "DOT", "Q", 136.84), quote("SOL", "USDT", "Q", 150.0)]);
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+
+    let before = incremental.best_paths().unwrap();
+    let changed = incremental.remove_provider(&"Q".to_owned()).unwrap();
+    graph.retain(|(pp, _)| pp.provider != "Q");
+    let after = FloydWarshallCalculator::calc_best_paths(&graph).unwrap();
+    assert_eq!(after, incremental.best_paths().unwrap());
+    assert_eq!(changed_pairs(&before, &after), changed);
+    assert!(changed.contains(&pair("SOL", "BTC")));
+
+    assert!(incremental.remove_provider(&"Q".to_owned()).unwrap().is_empty());
+    assert!(incremental.remove_provider(&"XYZ".to_owned()).unwrap().is_empty());
+}
+
+/// An update closing an arbitrage cycle is reported, and not applied.
+#[test]
+fn test_negative_cycle_rejected() {
+    let graph = real_life_graph();
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+    let (provider_pair, price) = quote("USDT", "DOT", "Q", 0.06); // 17.43 * 0.06 = 1.0458
+    match incremental.update(&provider_pair, price) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            assert_eq!(vec!["Q", "P"], cycle.steps.iter().map(|s| s.provider.as_str()).collect::<Vec<_>>());
+            assert!((cycle.total_cost as f64 / SCALE - 1.0458).abs() < 1e-9);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+    assert_eq!(FloydWarshallCalculator::calc_best_paths(&graph).unwrap(), incremental.best_paths().unwrap());
+}
+
+/// A rejected update with a new currency leaves it unknown, so that it may be queried and quoted again.
+#[test]
+fn test_rejected_new_currency() {
+    let mut graph = real_life_graph();
+    let mut incremental = IncrementalFloydWarshall::new(&graph).unwrap();
+    let (provider_pair, price) = quote("XYZ", "XYZ", "Q", 2.0);
+    assert!(matches!(incremental.update(&provider_pair, price), Err(CalculatorError::NegativeCyclesError(_))));
+    assert_eq!(None, incremental.best_path(&pair("XYZ", "XYZ")).unwrap());
+    assert_eq!(FloydWarshallCalculator::calc_best_paths(&graph).unwrap(), incremental.best_paths().unwrap());
+
+    let update = quote("USDT", "XYZ", "Q", 2.0);
+    incremental.update(&update.0, update.1).unwrap();
+    apply(&mut graph, update);
+    assert_eq!(FloydWarshallCalculator::calc_best_paths(&graph).unwrap(), incremental.best_paths().unwrap());
+}
+
+/// Hop limited paths are not kept in the tables, so are refused rather than ignored.
+#[test]
+fn test_max_hops_unsupported() {
+    let options = CalculatorOptions { max_hops: Some(1), ..Default::default() };
+    assert!(matches!(IncrementalFloydWarshall::with_options(&options, &real_life_graph()), Err(CalculatorError::UnsupportedOptionError)));
+}
+
+/// With currency decimals, as per the calculator, a new currency without decimals being refused.
+#[test]
+fn test_decimals() {
+    let mut graph = real_life_graph();
+    let decimals = ["BNB", "BTC", "DOT", "ETH", "USDT"].into_iter().zip([18, 8, 10, 18, 6]).map(|(c, d)| (c.to_owned(), d)).collect::<CurrencyDecimals<_>>();
+    let options = CalculatorOptions { decimals, ..Default::default() };
+    let mut incremental = IncrementalFloydWarshall::with_options(&options, &graph).unwrap();
+    assert_eq!(FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap(), incremental.best_paths().unwrap());
+
+    let (provider_pair, price) = quote("USDT", "XYZ", "Q", 2.0);
+    assert_eq!(Err(CalculatorError::ConversionError), incremental.update(&provider_pair, price));
+    assert_eq!(None, incremental.best_path(&pair("USDT", "XYZ")).unwrap());
+
+    let update = quote("BNB", "USDT", "Q", 300.0);
+    incremental.update(&update.0, update.1).unwrap();
+    apply(&mut graph, update);
+    assert_eq!(FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap(), incremental.best_paths().unwrap());
+}
+
+/// Summing costs, as per the calculator.
+#[test]
+fn test_sum_minimise() {
+    let mut graph = vec![
+        quote("A", "B", "P", 5.0),
+        quote("B", "C", "P", 3.0),
+        quote("A", "C", "P", 10.0),
+        quote("C", "D", "P", 1.0),
+    ];
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() };
+    let mut incremental = IncrementalFloydWarshall::with_options(&options, &graph).unwrap();
+
+    let update = quote("A", "C", "Q", 7.0);
+    let changed = incremental.update(&update.0, update.1).unwrap();
+    assert_eq!(BTreeSet::from([pair("A", "C"), pair("A", "D")]), changed);
+    apply(&mut graph, update);
+    assert_eq!(FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap(), incremental.best_paths().unwrap());
+}
@@ -0,0 +1,70 @@
+use core::marker::PhantomData;
+use super::algo::Tables;
+use super::super::Labels;
+use super::super::algo_types::TransformedGraph;
+use crate::types::*;
+use crate::*;
+
+/// All pairs best paths, as per `FloydWarshallCalculator`, built on lookup rather than upfront.
+///
+/// Keeps the Floyd-Warshall next-hop table and the sorted currencies and providers, in O(V²) memory, rather than the steps of
+/// every path, which may take O(V³). Each lookup walks the next hops of its pair, in O(V).
+pub struct LazyPricePathGraph<C: Currency, A: Amount, P: Provider> {
+    labels: Labels<C, P>,
+    transformed: TransformedGraph,
+    tables: Tables,
+    _amount: PhantomData<A>,
+}
+
+impl<C: Currency, A: Amount, P: Provider> LazyPricePathGraph<C, A, P> {
+    pub(crate) fn new(labels: Labels<C, P>, transformed: TransformedGraph, tables: Tables) -> Self {
+        Self { labels, transformed, tables, _amount: PhantomData }
+    }
+
+    /// Best path for a pair, if any. Fails with `OverflowError` if it does not fit `A`.
+    #[allow(clippy::type_complexity)]
+    pub fn get(&self, pair: &Pair<C>) -> Result<Option<PricePath<C, A, P>>, CalculatorError<C, A, P>> {
+        match (self.labels.currencies.binary_search(&pair.source), self.labels.currencies.binary_search(&pair.target)) {
+            (Ok(source), Ok(target)) => self.path(source, target).transpose(),
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether there is a path for a pair, without building it.
+    pub fn contains(&self, pair: &Pair<C>) -> bool {
+        match (self.labels.currencies.binary_search(&pair.source), self.labels.currencies.binary_search(&pair.target)) {
+            (Ok(source), Ok(target)) => self.tables.path_exists(source, target),
+            _ => false,
+        }
+    }
+
+    /// Pairs that have a path, in order.
+    pub fn pairs(&self) -> impl Iterator<Item = Pair<C>> + '_ {
+        let n = self.labels.currencies.len();
+        (0..n).flat_map(move |source| (0..n).map(move |target| (source, target)))
+            .filter(|&(source, target)| self.tables.path_exists(source, target))
+            .map(|(source, target)| Pair { source: self.labels.currencies[source].clone(), target: self.labels.currencies[target].clone() })
+    }
+
+    /// Pairs with their best paths, in order, each built as it is reached, as per `get`.
+    #[allow(clippy::type_complexity)]
+    pub fn iter(&self) -> impl Iterator<Item = Result<(Pair<C>, PricePath<C, A, P>), CalculatorError<C, A, P>>> + '_ {
+        let n = self.labels.currencies.len();
+        (0..n).flat_map(move |source| (0..n).map(move |target| (source, target)))
+            .filter_map(|(source, target)| {
+                let pair = Pair { source: self.labels.currencies[source].clone(), target: self.labels.currencies[target].clone() };
+                self.path(source, target).map(|path| Ok((pair, path?)))
+            })
+    }
+
+    /// Builds all paths upfront, as returned by `FloydWarshallCalculator::calc_best_paths`.
+    pub fn to_price_path_graph(&self) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        self.iter().collect()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn path(&self, source: usize, target: usize) -> Option<Result<PricePath<C, A, P>, CalculatorError<C, A, P>>> {
+        let path = self.transformed.restore(self.tables.path(source, target)?);
+        Some(self.labels.to_price_path(source, path))
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::AllPairsBestPathCalculator;
+use super::calculator::FloydWarshallCalculator;
+use crate::best_path_calculator::fixtures::{pair, quote, real_life_graph};
+
+/// Hop limited paths cannot be built from the next-hop table, so are refused rather than ignored.
+#[test]
+fn test_max_hops_unsupported() {
+    let options = CalculatorOptions { max_hops: Some(1), ..Default::default() };
+    assert_eq!(Some(CalculatorError::UnsupportedOptionError), FloydWarshallCalculator::calc_lazy_best_paths_with(&options, &real_life_graph()).err());
+}
+
+/// Converted eagerly, or looked up pair by pair, paths are the same as the calculator's.
+#[test]
+fn test_parity() {
+    let mut graph = real_life_graph();
+    graph.push(quote("BNB", "ETH", "Q", 0.1520));
+    for options in [
+        CalculatorOptions::default(),
+        CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+    ] {
+        let expected = FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap();
+        let lazy = FloydWarshallCalculator::calc_lazy_best_paths_with(&options, &graph).unwrap();
+        assert_eq!(expected, lazy.to_price_path_graph().unwrap());
+        assert_eq!(expected.keys().cloned().collect::<Vec<_>>(), lazy.pairs().collect::<Vec<_>>());
+        for (pair, path) in &expected {
+            assert!(lazy.contains(pair));
+            assert_eq!(Some(path), lazy.get(pair).unwrap().as_ref());
+        }
+    }
+}
+
+/// Pairs without a path, unreachable or of unknown currencies, are not found.
+#[test]
+fn test_missing_pairs() {
+    let mut graph = real_life_graph();
+    graph.push(quote("SOL", "USDT", "P", 150.0));
+    let lazy = FloydWarshallCalculator::calc_lazy_best_paths(&graph).unwrap();
+    assert!(lazy.contains(&pair("SOL", "BTC")));
+    for missing in [pair("BTC", "SOL"), pair("BTC", "XYZ"), pair("XYZ", "XYZ")] {
+        assert!(!lazy.contains(&missing));
+        assert_eq!(None, lazy.get(&missing).unwrap());
+    }
+}
+
+/// Arbitrage is reported upfront, as per the calculator.
+#[test]
+fn test_negative_cycle_reported() {
+    let mut graph = real_life_graph();
+    graph.push(quote("USDT", "DOT", "Q", 0.06));
+    match (FloydWarshallCalculator::calc_best_paths(&graph), FloydWarshallCalculator::calc_lazy_best_paths(&graph)) {
+        (Err(expected), Err(err)) => assert_eq!(expected, err),
+        _ => panic!("expected negative cycle"),
+    }
+}
+
+#[test]
+fn test_empty() {
+    let empty: &[(ProviderPair<&str, &str>, u128)] = &[];
+    let lazy = FloydWarshallCalculator::calc_lazy_best_paths(empty).unwrap();
+    assert!(lazy.to_price_path_graph().unwrap().is_empty());
+    assert_eq!(None, lazy.get(&Pair { source: "BTC", target: "BTC" }).unwrap());
+}
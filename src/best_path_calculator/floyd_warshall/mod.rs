@@ -1,7 +1,13 @@
-mod algo;
+pub(crate) mod algo;
 pub mod calculator;
+pub mod incremental;
+pub mod lazy;
 
 #[cfg(test)]
 mod algo_tests;
 #[cfg(test)]
-mod calculator_tests;
\ No newline at end of file
+mod calculator_tests;
+#[cfg(test)]
+mod incremental_tests;
+#[cfg(test)]
+mod lazy_tests;
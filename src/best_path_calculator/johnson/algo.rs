@@ -0,0 +1,69 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+pub(crate) use super::super::algo_types::{Edge, Pair, Path, PathCalculationError, TransformedGraph, adjacency_list, vertex_count};
+pub(crate) use crate::types::{Accumulation, Objective};
+use super::super::bellman_ford::algo::bellman_ford;
+use super::super::dijkstra::algo::dijkstra;
+
+/// Gets best paths for all pairs, as per `accumulation` and `objective`, running Johnson's core on the transformed graph.
+pub(crate) fn best_paths(edges: &[Edge], accumulation: Accumulation, objective: Objective) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    TransformedGraph::new(edges, accumulation, objective).run(johnson_core)
+}
+
+/// Johnson's all-pairs shortest paths. O(VE log V), vs Floyd-Warshall's O(V³) and O(V²) memory, hence suited to sparse graphs.
+///
+/// Bellman-Ford from a virtual vertex, connected to every vertex at zero cost, gives potentials h(v) that reweight each edge
+/// to a non-negative w(u,v) + h(u) - h(v), without changing which paths are shortest, as the potentials telescope along
+/// any path. Dijkstra then runs from every source. Negative cycles are caught by the Bellman-Ford pass.
+///
+/// Output as per Floyd-Warshall: `Pair → Path` for every reachable pair, including every vertex to itself.
+fn johnson_core(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    if edges.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    let n = vertex_count(edges);
+    let mut vertex_exists = vec![false; n];
+    for &e in edges {
+        vertex_exists[e.pair.source] = true;
+        vertex_exists[e.pair.target] = true;
+    }
+
+    // virtual vertex n, with a zero cost edge to every vertex
+    let virtual_edges = edges.iter().cloned()
+        .chain((0..n).filter(|&v| vertex_exists[v]).map(|v| Edge { pair: Pair { source: n, target: v }, provider: usize::MAX, cost: 0.0 }))
+        .collect::<Vec<_>>();
+    let potentials = bellman_ford(&virtual_edges, n)?;
+    let h = (0..n).map(|v| potentials.get(&v).map(|p| p.total_cost).unwrap_or(0.0)).collect::<Vec<_>>();
+
+    // clamp float noise on reweighted costs that should be exactly 0
+    let reweighted = edges.iter()
+        .map(|e| Edge { cost: (e.cost + h[e.pair.source] - h[e.pair.target]).max(0.0), ..*e })
+        .collect::<Vec<_>>();
+    let adjacency = adjacency_list(&reweighted, n);
+    let originals = edges.iter().map(|e| ((e.pair, e.provider), e.cost)).collect::<BTreeMap<_, _>>();
+
+    let mut result: BTreeMap<Pair, Path> = BTreeMap::new();
+    for source in (0..n).filter(|&v| vertex_exists[v]) {
+        for (target, path) in dijkstra(&adjacency, source) {
+            // swap back original costs, summing these rather than undoing the potentials keeps float noise out
+            let edges = path.edges.into_iter().map(|e| Edge { cost: originals[&(e.pair, e.provider)], ..e }).collect::<Vec<_>>();
+            let total_cost = edges.iter().map(|e| e.cost).sum();
+            result.insert(Pair { source, target }, Path { total_cost, edges });
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+pub(crate) fn shortest_paths(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    best_paths(edges, Accumulation::Sum, Objective::Minimise)
+}
+
+#[cfg(test)]
+pub(crate) fn longest_paths_by_product(edges: &[Edge]) -> Result<BTreeMap<Pair, Path>, PathCalculationError> {
+    best_paths(edges, Accumulation::Product, Objective::Maximise)
+}
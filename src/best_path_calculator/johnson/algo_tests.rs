@@ -0,0 +1,59 @@
+use super::algo::*;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Youtube graph, as per floyd_warshall::algo_tests — same costs, with a negative edge to exercise the reweighting.
+#[test]
+fn test_youtube_with_negative_edge() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 3.0},
+        Edge{pair: Pair{source: 0, target: 3}, provider: 1, cost: 7.0},
+        Edge{pair: Pair{source: 1, target: 0}, provider: 1, cost: 8.0},
+        Edge{pair: Pair{source: 1, target: 2}, provider: 1, cost: 2.0},
+        Edge{pair: Pair{source: 2, target: 0}, provider: 1, cost: -4.0},
+        Edge{pair: Pair{source: 2, target: 3}, provider: 1, cost: 1.0},
+        Edge{pair: Pair{source: 3, target: 0}, provider: 1, cost: 2.0},
+    ];
+    let res = shortest_paths(&edges).unwrap();
+    let costs = (0_usize..=3).map(|source|
+        (0_usize..=3).map(|target|
+            res.get(&Pair{source, target}).map(|p|p.total_cost)
+        ).collect::<Vec<_>>()
+    ).collect::<Vec<_>>();
+    assert_eq!(vec![
+        vec![Some(0.0),  Some(3.0), Some(5.0), Some(6.0)],
+        vec![Some(-2.0), Some(0.0), Some(2.0), Some(3.0)],
+        vec![Some(-4.0), Some(-1.0), Some(0.0), Some(1.0)],
+        vec![Some(2.0),  Some(5.0), Some(7.0), Some(0.0)],
+        ], costs);
+    assert_eq!(vec![edges[3], edges[4]], res[&Pair{source: 1, target: 0}].edges);
+}
+
+#[test]
+fn test_negative_cycle() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 3.0},
+        Edge{pair: Pair{source: 1, target: 2}, provider: 1, cost: 2.0},
+        Edge{pair: Pair{source: 2, target: 0}, provider: 1, cost: -6.0},
+    ];
+    match shortest_paths(&edges) {
+        Err(PathCalculationError::NegativeCyclesError(cycle)) => assert_eq!(-1.0, cycle.total_cost),
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+/// Same graph and results as floyd_warshall::algo_tests::test_simple.
+#[test]
+fn test_simple() {
+    let edges = vec![
+        Edge{pair: Pair{source: 0, target: 1}, provider: 1, cost: 2.0},
+        Edge{pair: Pair{source: 1, target: 0}, provider: 1, cost: 0.5},
+        Edge{pair: Pair{source: 1, target: 2}, provider: 1, cost: 4.0},
+        Edge{pair: Pair{source: 2, target: 1}, provider: 1, cost: 0.25},
+    ];
+    let res = longest_paths_by_product(&edges).unwrap();
+    assert_eq!(9, res.len());
+    assert_eq!(Path { total_cost: 8.0, edges: vec![edges[0], edges[2]] }, res[&Pair { source: 0, target: 2 }]);
+    assert_eq!(Path { total_cost: 0.125, edges: vec![edges[3], edges[1]] }, res[&Pair { source: 2, target: 0 }]);
+    assert_eq!(Path { total_cost: 1.0, edges: vec![] }, res[&Pair { source: 1, target: 1 }]);
+}
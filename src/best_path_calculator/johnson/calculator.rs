@@ -0,0 +1,37 @@
+use super::algo;
+use super::super::IndexedGraph;
+use super::super::bellman_ford::algo::best_paths_hop_limited;
+use crate::types::*;
+use crate::*;
+
+pub use super::super::SCALE;
+
+pub struct JohnsonCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> AllPairsBestPathCalculator<C, A, P> for JohnsonCalculator {
+
+    /// Calculates best paths for all currency pairs using Johnson's algorithm, as per `options`.
+    /// O(VE log V) with O(V + E) working memory, vs Floyd-Warshall's O(V³) and O(V²) — preferable for sparse quote graphs.
+    /// Output as per `FloydWarshallCalculator`, including for `max_hops`.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::johnson::calculator::JohnsonCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ];
+    /// let res = JohnsonCalculator::calc_best_paths(in_graph).unwrap();
+    /// assert_eq!(999_701_550_000_u128, res.get(&Pair { source: "BNB", target: "ETH" }).unwrap().total_cost);
+    /// ```
+    fn calc_best_paths_with(options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        let res = match options.max_hops {
+            Some(max_hops) => best_paths_hop_limited(&graph.edges, options.accumulation, options.objective, max_hops),
+            None => algo::best_paths(&graph.edges, options.accumulation, options.objective),
+        }.map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph(res)
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::AllPairsBestPathCalculator;
+use super::calculator::JohnsonCalculator;
+use crate::best_path_calculator::floyd_warshall::calculator::FloydWarshallCalculator;
+use crate::best_path_calculator::fixtures::{quote, real_life_graph};
+
+/// Johnson must return the very same graph as Floyd-Warshall, for every accumulation and objective that is bounded, and with a hop limit.
+#[test]
+fn test_parity_with_floyd_warshall() {
+    let mut graph = real_life_graph();
+    graph.push(quote("BNB", "ETH", "Q", 0.1520));
+    for options in [
+        CalculatorOptions::default(),
+        CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+        CalculatorOptions { max_hops: Some(2), ..Default::default() },
+    ] {
+        assert_eq!(
+            FloydWarshallCalculator::calc_best_paths_with(&options, &graph).unwrap(),
+            JohnsonCalculator::calc_best_paths_with(&options, &graph).unwrap(),
+        );
+    }
+}
+
+/// Arbitrage is reported via the Bellman-Ford reweighting pass.
+#[test]
+fn test_negative_cycle_reported() {
+    let mut graph = real_life_graph();
+    graph.push(quote("USDT", "DOT", "Q", 0.06));
+    match JohnsonCalculator::calc_best_paths(&graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => assert!(cycle.total_cost > 1_000_000_000_000),
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_empty() {
+    let empty: &[(ProviderPair<&str, &str>, u128)] = &[];
+    assert!(JohnsonCalculator::calc_best_paths(empty).unwrap().is_empty());
+}
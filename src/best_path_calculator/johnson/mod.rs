@@ -0,0 +1,7 @@
+mod algo;
+pub mod calculator;
+
+#[cfg(test)]
+mod algo_tests;
+#[cfg(test)]
+mod calculator_tests;
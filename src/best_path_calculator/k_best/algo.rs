@@ -0,0 +1,129 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+pub(crate) use super::super::algo_types::{Edge, Path, PathCalculationError, TransformedGraph, adjacency_list, rank, vertex_count};
+use super::super::bellman_ford::algo::bellman_ford;
+use crate::types::{Accumulation, Objective};
+
+/// Up to `k` best loopless paths from `source` to `target`, of at most `max_hops` edges if limited, as per accumulation and
+/// objective, best first. Providers are told apart, so the same currencies via a different provider is an alternative path.
+pub(crate) fn k_best_paths(
+    edges: &[Edge],
+    source: usize,
+    target: usize,
+    k: usize,
+    max_hops: Option<usize>,
+    accumulation: Accumulation,
+    objective: Objective,
+) -> Result<Vec<Path>, PathCalculationError> {
+    let transformed = TransformedGraph::per_provider(edges, accumulation, objective);
+    let paths = yen(&transformed.edges, source, target, k, max_hops).map_err(|err| transformed.restore_error(err))?;
+    Ok(paths.into_iter().map(|path| transformed.restore(path)).collect())
+}
+
+/// Yen's algorithm: each next best path deviates from one already found at some spur vertex, sharing its root up to there.
+/// Candidate spur paths avoid the root's vertices, to stay loopless, and the edges taken by earlier paths with the same root,
+/// to differ from them. Spur paths are found by Bellman-Ford, as costs may be negative. Removing edges and vertices cannot
+/// create negative cycles, so only those reachable from `source` in the full graph are reported.
+///
+/// With `max_hops`, spur paths are limited to the hops left after their root, and found by a search over the loopless
+/// paths within that limit instead, as the best hop limited walk may pass through a negative cycle. As per
+/// `bellman_ford_hop_limited`, negative cycles are then not reported.
+pub(crate) fn yen(edges: &[Edge], source: usize, target: usize, k: usize, max_hops: Option<usize>) -> Result<Vec<Path>, PathCalculationError> {
+    let n = vertex_count(edges);
+    if k == 0 || source >= n || target >= n {
+        return Ok(vec![]);
+    }
+
+    let first = match best_path(edges, source, target, max_hops)? {
+        Some(path) => path,
+        None => return Ok(vec![]),
+    };
+
+    let mut found = vec![first];
+    let mut candidates: Vec<Path> = vec![];
+    while found.len() < k {
+        let last = found.last().unwrap().clone();
+        let mut removed_vertex = vec![false; n];
+        for i in 0..last.edges.len() {
+            let root = &last.edges[..i];
+            let spur_vertex = if i == 0 { source } else { last.edges[i - 1].pair.target };
+            if i > 0 {
+                removed_vertex[last.edges[i - 1].pair.source] = true;
+            }
+
+            let removed_edges = found.iter()
+                .filter(|p| p.edges.len() > i && &p.edges[..i] == root)
+                .map(|p| p.edges[i])
+                .collect::<Vec<_>>();
+            let spur_edges = edges.iter()
+                .filter(|e| !removed_vertex[e.pair.source] && !removed_vertex[e.pair.target] && !removed_edges.contains(e))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if let Some(spur) = best_path(&spur_edges, spur_vertex, target, max_hops.map(|max_hops| max_hops - i))? {
+                let path_edges = root.iter().chain(spur.edges.iter()).cloned().collect::<Vec<_>>();
+                let path = Path { total_cost: path_edges.iter().map(|e| e.cost).sum(), edges: path_edges };
+                if !candidates.contains(&path) && !found.contains(&path) {
+                    candidates.push(path);
+                }
+            }
+        }
+
+        let best = candidates.iter().enumerate().min_by(|(_, a), (_, b)| rank(a, b)).map(|(ind, _)| ind);
+        match best {
+            Some(ind) => found.push(candidates.swap_remove(ind)),
+            None => break,
+        }
+    }
+    Ok(found)
+}
+
+/// Best path source→target, by Bellman-Ford, or of at most `max_hops` edges if limited, by `best_loopless_path`.
+fn best_path(edges: &[Edge], source: usize, target: usize, max_hops: Option<usize>) -> Result<Option<Path>, PathCalculationError> {
+    match max_hops {
+        Some(max_hops) => Ok(best_loopless_path(edges, source, target, max_hops)),
+        None => Ok(bellman_ford(edges, source)?.remove(&target)),
+    }
+}
+
+/// Best loopless path source→target of at most `max_hops` edges, as per `rank`, None if there is none.
+///
+/// A depth-first search over every such path, extending the current path only through vertices not yet on it, so
+/// exponential in `max_hops` in the worst case.
+fn best_loopless_path(edges: &[Edge], source: usize, target: usize, max_hops: usize) -> Option<Path> {
+    let n = vertex_count(edges).max(source + 1);
+    let adjacency = adjacency_list(edges, n);
+
+    let mut best: Option<Path> = None;
+    let mut on_path = vec![false; n];
+    // path[d] is the edge into stack[d + 1]; stack holds (vertex, index of the next outgoing edge to explore)
+    let mut path: Vec<Edge> = vec![];
+    let mut stack: Vec<(usize, usize)> = vec![(source, 0)];
+    on_path[source] = true;
+    while let Some(&(v, i)) = stack.last() {
+        let next = if v == target || path.len() == max_hops { None } else { adjacency[v].get(i) };
+        match next {
+            Some(&e) => {
+                stack.last_mut().unwrap().1 += 1;
+                let w = e.pair.target;
+                if !on_path[w] {
+                    on_path[w] = true;
+                    path.push(e);
+                    stack.push((w, 0));
+                }
+            }
+            None => {
+                if v == target {
+                    let candidate = Path { total_cost: path.iter().map(|e| e.cost).sum(), edges: path.clone() };
+                    if best.as_ref().map(|best| rank(&candidate, best).is_lt()).unwrap_or(true) {
+                        best = Some(candidate);
+                    }
+                }
+                on_path[v] = false;
+                stack.pop();
+                path.pop();
+            }
+        }
+    }
+    best
+}
@@ -0,0 +1,98 @@
+use super::algo::*;
+use crate::best_path_calculator::fixtures::edge;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Classic example from https://en.wikipedia.org/wiki/Yen%27s_algorithm, with C..H as 0..5.
+#[test]
+fn test_wikipedia_example() {
+    let edges = vec![
+        edge(0, 1, 0, 3.0), // C→D
+        edge(0, 2, 0, 2.0), // C→E
+        edge(1, 3, 0, 4.0), // D→F
+        edge(2, 1, 0, 1.0), // E→D
+        edge(2, 3, 0, 2.0), // E→F
+        edge(2, 4, 0, 3.0), // E→G
+        edge(3, 4, 0, 2.0), // F→G
+        edge(3, 5, 0, 1.0), // F→H
+        edge(4, 5, 0, 2.0), // G→H
+    ];
+    let paths = yen(&edges, 0, 5, 3, None).unwrap();
+    assert_eq!(vec![5.0, 7.0, 8.0], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+    assert_eq!(vec![edges[1], edges[4], edges[7]], paths[0].edges);                // C→E→F→H
+    assert_eq!(vec![edges[1], edges[5], edges[8]], paths[1].edges);                // C→E→G→H
+    assert_eq!(vec![edges[0], edges[2], edges[7]], paths[2].edges);                // C→D→F→H, ties with C→E→D→F→H on cost, fewer hops
+}
+
+/// Paths longer than `max_hops` are passed over for the next best within the limit, down to none.
+#[test]
+fn test_max_hops() {
+    let edges = vec![
+        edge(0, 1, 0, 3.0), // C→D
+        edge(0, 2, 0, 2.0), // C→E
+        edge(1, 3, 0, 4.0), // D→F
+        edge(2, 1, 0, 1.0), // E→D
+        edge(2, 3, 0, 2.0), // E→F
+        edge(2, 4, 0, 3.0), // E→G
+        edge(3, 4, 0, 2.0), // F→G
+        edge(3, 5, 0, 1.0), // F→H
+        edge(4, 5, 0, 2.0), // G→H
+    ];
+    assert_eq!(7, yen(&edges, 0, 5, 10, None).unwrap().len());
+    let paths = yen(&edges, 0, 5, 10, Some(3)).unwrap();
+    assert_eq!(vec![5.0, 7.0, 8.0], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+    assert_eq!(vec![edges[0], edges[2], edges[7]], paths[2].edges);                // C→D→F→H, rather than the 4 hops C→E→D→F→H
+    assert!(yen(&edges, 0, 5, 10, Some(2)).unwrap().is_empty());
+}
+
+/// Within `max_hops`, the best walk 0→1→2→1→3 passes through the negative cycle 1→2→1, the loopless paths are still found.
+#[test]
+fn test_max_hops_negative_cycle() {
+    let edges = vec![
+        edge(0, 1, 0, 1.0),
+        edge(1, 2, 0, -5.0),
+        edge(2, 1, 0, 1.0),
+        edge(1, 3, 0, 1.0),
+        edge(0, 3, 0, 10.0),
+    ];
+    assert!(yen(&edges, 0, 3, 10, None).is_err());
+    let paths = yen(&edges, 0, 3, 10, Some(4)).unwrap();
+    assert_eq!(vec![2.0, 10.0], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+    assert_eq!(vec![edges[0], edges[3]], paths[0].edges);
+}
+
+/// Asking for more paths than exist returns all of them, each loopless and distinct.
+#[test]
+fn test_fewer_than_k() {
+    let edges = vec![
+        edge(0, 1, 0, 1.0),
+        edge(1, 0, 0, 1.0),
+        edge(1, 2, 0, 1.0),
+        edge(1, 2, 1, 2.0), // same pair, another provider
+        edge(0, 2, 0, 5.0),
+    ];
+    let paths = yen(&edges, 0, 2, 10, None).unwrap();
+    assert_eq!(vec![2.0, 3.0, 5.0], paths.iter().map(|p| p.total_cost).collect::<Vec<_>>());
+    assert_eq!(1, paths[1].edges[1].provider);
+    for path in &paths {
+        let mut vertices = path.edges.iter().map(|e| e.pair.target).collect::<Vec<_>>();
+        vertices.push(0);
+        vertices.sort();
+        vertices.dedup();
+        assert_eq!(path.edges.len() + 1, vertices.len());
+    }
+}
+
+#[test]
+fn test_no_path() {
+    let edges = vec![edge(0, 1, 0, 1.0), edge(2, 1, 0, 1.0)];
+    assert!(yen(&edges, 0, 2, 3, None).unwrap().is_empty());
+    assert!(yen(&edges, 0, 1, 0, None).unwrap().is_empty());
+    assert!(yen(&edges, 0, 99, 3, None).unwrap().is_empty());
+}
+
+#[test]
+fn test_negative_cycle() {
+    let edges = vec![edge(0, 1, 0, 1.0), edge(1, 2, 0, -2.0), edge(2, 1, 0, 1.0)];
+    assert!(matches!(yen(&edges, 0, 2, 2, None), Err(PathCalculationError::NegativeCyclesError(_))));
+}
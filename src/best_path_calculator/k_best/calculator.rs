@@ -0,0 +1,53 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+use super::algo;
+use super::super::IndexedGraph;
+use crate::types::*;
+use crate::*;
+
+pub use super::super::SCALE;
+
+pub struct YenCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> KBestPathCalculator<C, A, P> for YenCalculator {
+
+    /// Calculates up to `k` best loopless paths from `source` to `target` using Yen's algorithm, as per `options`, best first.
+    /// Unlike the best path calculators, multiple providers per pair are kept, so routing the same currencies via another
+    /// provider counts as an alternative. Fewer than `k` paths are returned if no more exist. With `max_hops`, no path has
+    /// more than that many steps, and negative cycles are not reported.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::k_best::calculator::YenCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "ETH" }, provider: "BINANCE" }, 150_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "ETH" }, provider: "KRAKEN" }, 140_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "BINANCE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 420_000_000_u128),
+    /// ];
+    /// let paths = YenCalculator::calc_k_best_paths(&"BNB", &"ETH", 3, in_graph).unwrap();
+    /// assert_eq!(
+    ///     vec![152_959_800_000_u128, 150_000_000_000_u128, 140_000_000_000_u128],
+    ///     paths.iter().map(|p| p.total_cost).collect::<Vec<_>>()
+    /// );
+    /// ```
+    fn calc_k_best_paths_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        k: usize,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>> {
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        let (source_idx, target_idx) = match (graph.currency_indexer.try_by_val(source), graph.currency_indexer.try_by_val(target)) {
+            (Some(source_idx), Some(target_idx)) => (source_idx, target_idx),
+            _ => return Ok(vec![]),
+        };
+
+        let res = algo::k_best_paths(&graph.edges, source_idx, target_idx, k, options.max_hops, options.accumulation, options.objective)
+            .map_err(|err| graph.to_calculator_error(err))?;
+        res.into_iter().map(|path| graph.to_price_path(source_idx, path)).collect()
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::{AllPairsBestPathCalculator, KBestPathCalculator};
+use super::calculator::YenCalculator;
+use crate::best_path_calculator::floyd_warshall::calculator::FloydWarshallCalculator;
+use crate::best_path_calculator::fixtures::{quote, real_life_graph};
+
+/// The best of the k paths is the calculators' best path, followed by worse alternatives, including via another provider.
+#[test]
+fn test_k_best() {
+    let mut graph = real_life_graph();
+    graph.push(quote("BNB", "ETH", "Q", 0.1520));
+    let (source, target) = ("BNB".to_owned(), "ETH".to_owned());
+    let best = FloydWarshallCalculator::calc_best_paths(&graph).unwrap().remove(&Pair { source: source.clone(), target: target.clone() }).unwrap();
+
+    let paths = YenCalculator::calc_k_best_paths(&source, &target, 4, &graph).unwrap();
+    assert_eq!(4, paths.len());
+    assert_eq!(best, paths[0]);
+    assert!(paths.windows(2).all(|w| w[0].total_cost >= w[1].total_cost));
+    assert!(paths.iter().all(|p| p.steps.first().unwrap().pair.source == source && p.steps.last().unwrap().pair.target == target));
+    assert!(paths.iter().any(|p| p.steps.len() == 1 && p.steps[0].provider == "Q"));
+    for (i, a) in paths.iter().enumerate() {
+        assert!(paths[i + 1..].iter().all(|b| a != b));
+    }
+}
+
+/// Minimising a sum ranks the cheapest first.
+#[test]
+fn test_sum_minimise() {
+    let graph = vec![
+        (ProviderPair { pair: Pair { source: "A", target: "B" }, provider: "P" }, 5_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "B", target: "C" }, provider: "P" }, 3_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "P" }, 10_000_000_000_000_u128),
+        (ProviderPair { pair: Pair { source: "A", target: "C" }, provider: "Q" }, 9_000_000_000_000_u128),
+    ];
+    let options = CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() };
+    let paths = YenCalculator::calc_k_best_paths_with(&options, &"A", &"C", 5, &graph).unwrap();
+    assert_eq!(
+        vec![8_000_000_000_000_u128, 9_000_000_000_000_u128, 10_000_000_000_000_u128],
+        paths.iter().map(|p| p.total_cost).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_unknown_currency_returns_empty() {
+    let graph = real_life_graph();
+    assert!(YenCalculator::calc_k_best_paths(&"BNB".to_owned(), &"XYZ".to_owned(), 3, &graph).unwrap().is_empty());
+}
@@ -1,14 +1,34 @@
 pub mod noop_calculator;
 pub mod floyd_warshall;
 pub mod bellman_ford;
+pub mod johnson;
+pub mod dijkstra;
+pub mod spfa;
+pub mod arbitrage;
+pub mod k_best;
+pub mod disjoint;
+pub mod batch;
+#[cfg(feature = "deterministic")]
+pub mod deterministic;
 pub(crate) mod algo_types;
+pub(crate) mod wide;
 
+#[cfg(test)]
+mod wide_tests;
+
+#[cfg(test)]
+pub(crate) mod fixtures;
+
+/// Default fixed-point scale of amounts, as per `CalculatorOptions::scale`.
 pub const SCALE: f64 = 1_000_000_000_000.0;
 
 #[cfg(not(feature = "std"))]
-use alloc::{collections::{BTreeMap, BTreeSet}, vec::Vec};
+use alloc::{collections::{BTreeMap, BTreeSet}, vec, vec::Vec};
 #[cfg(feature = "std")]
 use std::collections::{BTreeMap, BTreeSet};
+use crate::types::*;
+use crate::{CalculatorOptions, PricePathGraph, WidestPathGraph};
+use algo_types::Path;
 
 /// Utility for indexing a set of values by sorted position.
 /// Provides O(log n) lookup by value and O(1) lookup by index.
@@ -35,7 +55,257 @@ impl<'a, T: Ord + Clone> PositionIndexer<'a, T> {
         self.map.get(val).copied()
     }
 
+    pub(crate) fn len(&self) -> usize {
+        self.vec.len()
+    }
+
     pub(crate) fn by_ind(&self, ind: usize) -> T {
         self.vec.get(ind).map(|&x| x.clone()).unwrap()
     }
 }
+
+/// Amounts as supplied, per pair and provider, so that paths are unwrapped into the very amounts quoted, and `total_cost`
+/// accumulated from these exactly, as per `options`, rather than round tripped through f64.
+///
+/// With currency decimals, amounts are ratios of base units, in scale of `scale`, each currency having a unit of
+/// 10^decimals base units, as per `currency_unit`. Products then normalise into rates by the units of their pair.
+#[derive(Clone)]
+pub(crate) struct Amounts {
+    by_quote: BTreeMap<(algo_types::Pair, usize), Vec<u128>>,
+    accumulation: Accumulation,
+    rounding: Rounding,
+    scale: u128,
+    /// unit per currency index, none without decimals
+    units: Vec<u128>,
+}
+
+impl Amounts {
+    /// Fails for a scale of 0, as no amount then maps to a rate.
+    pub(crate) fn new<C: Currency, A: Amount, P: Provider>(options: &CalculatorOptions<C>) -> Result<Self, CalculatorError<C, A, P>> {
+        if options.scale == 0 {
+            return Err(CalculatorError::ConversionError);
+        }
+        Ok(Self { by_quote: BTreeMap::new(), accumulation: options.accumulation, rounding: options.rounding, scale: options.scale, units: vec![] })
+    }
+
+    fn unit(&self, currency: usize) -> u128 {
+        self.units.get(currency).copied().unwrap_or(self.scale)
+    }
+
+    /// Rate of a base unit ratio of 1, ie. 1 when summing or without decimals.
+    fn factor(&self, algo_types::Pair { source, target }: algo_types::Pair) -> f64 {
+        match self.accumulation {
+            Accumulation::Product => self.unit(source) as f64 / self.unit(target) as f64,
+            Accumulation::Sum => 1.0,
+        }
+    }
+
+    /// Rate in scale of 1 of an amount of a pair, in scale of `scale`, as taken by the algorithms.
+    pub(crate) fn to_rate(&self, pair: algo_types::Pair, amount: u128) -> f64 {
+        amount as f64 / self.scale as f64 * self.factor(pair)
+    }
+
+    /// Adds the amount of a quote, `set` replacing any other of its pair and provider.
+    pub(crate) fn insert(&mut self, pair: algo_types::Pair, provider: usize, amount: u128, set: bool) {
+        let amounts = self.by_quote.entry((pair, provider)).or_default();
+        if set {
+            amounts.clear();
+        }
+        amounts.push(amount);
+    }
+
+    pub(crate) fn remove(&mut self, pair: algo_types::Pair, provider: usize) -> Option<Vec<u128>> {
+        self.by_quote.remove(&(pair, provider))
+    }
+
+    pub(crate) fn restore(&mut self, pair: algo_types::Pair, provider: usize, amounts: Option<Vec<u128>>) {
+        match amounts {
+            Some(amounts) => self.by_quote.insert((pair, provider), amounts),
+            None => self.by_quote.remove(&(pair, provider)),
+        };
+    }
+
+    /// Amount of an edge of original cost, ie. of the first quote of its pair and provider at that rate, None if no quote
+    /// is at that rate.
+    fn of(&self, e: &algo_types::Edge) -> Option<u128> {
+        self.by_quote.get(&(e.pair, e.provider))
+            .and_then(|amounts| amounts.iter().copied().find(|&amount| self.to_rate(e.pair, amount) == e.cost))
+    }
+}
+
+/// Base units of one unit of `currency`, 10^decimals as per `options.decimals`, or `options.scale` without decimals.
+/// Fails with `ConversionError` for a currency missing from non-empty decimals, or where 10^decimals overflows u128.
+pub(crate) fn currency_unit<C: Currency, A: Amount, P: Provider>(options: &CalculatorOptions<C>, currency: &C) -> Result<u128, CalculatorError<C, A, P>> {
+    if options.decimals.is_empty() {
+        return Ok(options.scale);
+    }
+    options.decimals.get(currency)
+        .and_then(|&decimals| 10_u128.checked_pow(decimals.into()))
+        .ok_or(CalculatorError::ConversionError)
+}
+
+/// Unwraps the edges of an internal path from `source`, of original costs, into currencies and providers, as looked up by
+/// index, and the amounts quoted, with `total_cost` accumulated from these exactly. Products are in units of the target,
+/// ie. one unit of the source, times the amounts, in scale of `scale`, which is `scale` for self references without decimals.
+/// Fails with `OverflowError` of the path's pair if `total_cost` does not fit `A`, or of a step's pair if its cost does not,
+/// and with `ConversionError` if an edge is of no quote.
+pub(crate) fn to_price_path<C: Currency, A: Amount, P: Provider>(
+    source: usize,
+    edges: Vec<algo_types::Edge>,
+    amounts: &Amounts,
+    currency: impl Fn(usize) -> C,
+    provider: impl Fn(usize) -> P,
+) -> Result<PricePath<C, A, P>, CalculatorError<C, A, P>> {
+    let costs = edges.iter().map(|e| amounts.of(e)).collect::<Option<Vec<_>>>().ok_or(CalculatorError::ConversionError)?;
+    let total_cost = match amounts.accumulation {
+        Accumulation::Product => core::iter::once(amounts.unit(source)).chain(costs.iter().copied()).collect::<Vec<_>>(),
+        Accumulation::Sum => costs.clone(),
+    };
+    let target = edges.last().map_or(source, |e| e.pair.target);
+    let total_cost = wide::accumulate(total_cost.into_iter(), amounts.scale, amounts.accumulation, amounts.rounding)
+        .and_then(|total_cost| A::try_from(total_cost).ok())
+        .ok_or_else(|| CalculatorError::OverflowError(Pair { source: currency(source), target: currency(target) }))?;
+    let steps = edges.into_iter().zip(costs).map(|(algo_types::Edge { pair: algo_types::Pair { source, target }, provider: provider_ind, .. }, cost)| {
+        let pair = Pair { source: currency(source), target: currency(target) };
+        match A::try_from(cost) {
+            Ok(cost) => Ok(PathStep { pair, provider: provider(provider_ind), cost }),
+            Err(_) => Err(CalculatorError::OverflowError(pair)),
+        }
+    }).collect::<Result<Vec<_>, _>>()?;
+    Ok(PricePath { total_cost, steps })
+}
+
+/// Input graph wrapped into primitive indexed internal representation: currencies and providers replaced by their
+/// sorted positions, prices by rates in scale of 1, as per the scale of `options`.
+pub(crate) struct IndexedGraph<'a, C, P> {
+    pub(crate) currency_indexer: PositionIndexer<'a, C>,
+    pub(crate) provider_indexer: PositionIndexer<'a, P>,
+    pub(crate) edges: Vec<algo_types::Edge>,
+    pub(crate) amounts: Amounts,
+}
+
+impl<'a, C: Currency, P: Provider> IndexedGraph<'a, C, P> {
+    /// Indexes quotes, to be unwrapped as per `options`, in base units of currencies with decimals, if any.
+    pub(crate) fn new<A: Amount>(options: &CalculatorOptions<C>, pairs_and_prices: &'a [(ProviderPair<C, P>, A)]) -> Result<Self, CalculatorError<C, A, P>> {
+        Self::from_quotes(options, pairs_and_prices.iter().map(|(pp, cost)| (pp, *cost)))
+    }
+
+    /// As per `new`, for quotes carrying a capacity too, returned separately in the order of the edges.
+    pub(crate) fn with_capacities<A: Amount>(quotes: &'a [(ProviderPair<C, P>, A, A)]) -> Result<(Self, Vec<u128>), CalculatorError<C, A, P>> {
+        let graph = Self::from_quotes(&CalculatorOptions::default(), quotes.iter().map(|(pp, rate, _)| (pp, *rate)))?;
+        let capacities = quotes.iter()
+            .map(|(_, _, capacity)| TryInto::<u128>::try_into(*capacity).map_err(|_| CalculatorError::ConversionError))
+            .collect::<Result<Vec<_>, CalculatorError<C, A, P>>>()?;
+        Ok((graph, capacities))
+    }
+
+    fn from_quotes<A: Amount>(
+        options: &CalculatorOptions<C>,
+        quotes: impl Iterator<Item = (&'a ProviderPair<C, P>, A)> + Clone,
+    ) -> Result<Self, CalculatorError<C, A, P>> {
+        // get unique and indexed currencies and providers
+        let currency_indexer = PositionIndexer::new(quotes.clone().flat_map(|(ProviderPair { pair: Pair { source, target }, .. }, _)| vec![source, target].into_iter()));
+        let provider_indexer = PositionIndexer::new(quotes.clone().map(|(ProviderPair { provider, .. }, _)| provider));
+
+        // construct the edges — O(E), not O(currencies × E)
+        let mut amounts = Amounts::new(options)?;
+        if !options.decimals.is_empty() {
+            amounts.units = (0..currency_indexer.len())
+                .map(|ind| currency_unit(options, &currency_indexer.by_ind(ind)))
+                .collect::<Result<Vec<_>, CalculatorError<C, A, P>>>()?;
+        }
+        let edges = quotes.map(|(pp, cost)| {
+            let amount = TryInto::<u128>::try_into(cost).map_err(|_| CalculatorError::ConversionError)?;
+            let pair = algo_types::Pair { source: currency_indexer.by_val(&pp.pair.source), target: currency_indexer.by_val(&pp.pair.target) };
+            let edge = algo_types::Edge {
+                pair,
+                provider: provider_indexer.by_val(&pp.provider),
+                cost:     amounts.to_rate(pair, amount),
+            };
+            amounts.insert(edge.pair, edge.provider, amount, false);
+            Ok(edge)
+        }).collect::<Result<Vec<_>, CalculatorError<C, A, P>>>()?;
+
+        Ok(Self { currency_indexer, provider_indexer, edges, amounts })
+    }
+
+    /// Unwraps an internal path from `source`, as per `to_price_path`.
+    pub(crate) fn to_price_path<A: Amount>(&self, source: usize, Path { edges, .. }: Path) -> Result<PricePath<C, A, P>, CalculatorError<C, A, P>> {
+        to_price_path(source, edges, &self.amounts, |ind| self.currency_indexer.by_ind(ind), |ind| self.provider_indexer.by_ind(ind))
+    }
+
+    /// Unwraps all pairs results, as per `to_price_path`.
+    pub(crate) fn to_price_path_graph<A: Amount>(&self, paths: BTreeMap<algo_types::Pair, Path>) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        paths.into_iter().map(|(algo_types::Pair { source, target }, path)| {
+            let pair = Pair { source: self.currency_indexer.by_ind(source), target: self.currency_indexer.by_ind(target) };
+            Ok((pair, self.to_price_path(source, path)?))
+        }).collect()
+    }
+
+    /// Unwraps single source results, keyed by target, as per `to_price_path`.
+    pub(crate) fn to_price_path_graph_from<A: Amount>(&self, source: usize, paths: BTreeMap<usize, Path>) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        let source_currency = self.currency_indexer.by_ind(source);
+        paths.into_iter().map(|(target, path)| {
+            let pair = Pair { source: source_currency.clone(), target: self.currency_indexer.by_ind(target) };
+            Ok((pair, self.to_price_path(source, path)?))
+        }).collect()
+    }
+
+    /// Unwraps a widest path, with `total_cost` being the product of its rates.
+    /// Fails with `OverflowError` of the path's pair if its capacity does not fit `A`.
+    pub(crate) fn to_widest_path<A: Amount>(&self, algo_types::WidePath { capacity, edges }: algo_types::WidePath) -> Result<WidestPath<C, A, P>, CalculatorError<C, A, P>> {
+        let (source, target) = (first_source(&edges), edges.last().map_or(0, |e| e.pair.target));
+        let capacity = A::try_from(capacity)
+            .map_err(|_| CalculatorError::OverflowError(Pair { source: self.currency_indexer.by_ind(source), target: self.currency_indexer.by_ind(target) }))?;
+        let path = to_price_path(source, edges, &self.amounts, |ind| self.currency_indexer.by_ind(ind), |ind| self.provider_indexer.by_ind(ind))?;
+        Ok(WidestPath { capacity, path })
+    }
+
+    /// Unwraps widest paths, keyed by pair, as per `to_widest_path`.
+    pub(crate) fn to_widest_path_graph<A: Amount>(&self, paths: BTreeMap<algo_types::Pair, algo_types::WidePath>) -> Result<WidestPathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        paths.into_iter().map(|(algo_types::Pair { source, target }, path)| {
+            let pair = Pair { source: self.currency_indexer.by_ind(source), target: self.currency_indexer.by_ind(target) };
+            Ok((pair, self.to_widest_path(path)?))
+        }).collect()
+    }
+
+    /// Unwraps the negative cycle, or fails as per `to_price_path`.
+    pub(crate) fn to_calculator_error<A: Amount>(&self, algo_types::PathCalculationError::NegativeCyclesError(cycle): algo_types::PathCalculationError) -> CalculatorError<C, A, P> {
+        self.to_price_path(first_source(&cycle.edges), cycle).map_or_else(|err| err, CalculatorError::NegativeCyclesError)
+    }
+
+    /// Keeps the currencies and providers by index, along with the amounts, to unwrap paths calculated later on.
+    pub(crate) fn into_labels(self) -> Labels<C, P> {
+        Labels {
+            currencies: (0..self.currency_indexer.len()).map(|ind| self.currency_indexer.by_ind(ind)).collect(),
+            providers: (0..self.provider_indexer.len()).map(|ind| self.provider_indexer.by_ind(ind)).collect(),
+            amounts: self.amounts,
+        }
+    }
+}
+
+/// Currencies and providers by index, along with the amounts quoted, as kept by graphs queried beyond their calculation,
+/// eg. `PreparedGraph`, to unwrap internal paths as per `IndexedGraph`.
+pub(crate) struct Labels<C, P> {
+    /// sorted, unless added to later on
+    pub(crate) currencies: Vec<C>,
+    pub(crate) providers: Vec<P>,
+    pub(crate) amounts: Amounts,
+}
+
+impl<C: Currency, P: Provider> Labels<C, P> {
+    /// Unwraps an internal path from `source`, of original costs, as per `to_price_path`.
+    pub(crate) fn to_price_path<A: Amount>(&self, source: usize, Path { edges, .. }: Path) -> Result<PricePath<C, A, P>, CalculatorError<C, A, P>> {
+        to_price_path(source, edges, &self.amounts, |ind| self.currencies[ind].clone(), |ind| self.providers[ind].clone())
+    }
+
+    /// Unwraps the negative cycle, of original costs, or fails as per `to_price_path`.
+    pub(crate) fn to_calculator_error<A: Amount>(&self, algo_types::PathCalculationError::NegativeCyclesError(cycle): algo_types::PathCalculationError) -> CalculatorError<C, A, P> {
+        self.to_price_path(first_source(&cycle.edges), cycle).map_or_else(|err| err, CalculatorError::NegativeCyclesError)
+    }
+}
+
+/// Source of the first step, eg. of a cycle, where not known otherwise.
+pub(crate) fn first_source(edges: &[algo_types::Edge]) -> usize {
+    edges.first().map_or(0, |e| e.pair.source)
+}
@@ -6,7 +6,7 @@ use crate::*;
 /// No-op all-pairs calculator: returns each input pair as a single-step path with no routing.
 pub struct NoOpAllPairsCalculator {}
 impl<C: Currency, A: Amount, P: Provider> AllPairsBestPathCalculator<C, A, P> for NoOpAllPairsCalculator {
-	fn calc_best_paths(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError> {
+	fn calc_best_paths_with(_options: &CalculatorOptions<C>, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
 		Ok(pairs_and_prices.iter().cloned().map(|(pp, price)| (Pair{source: pp.pair.source, target: pp.pair.target}, PricePath{total_cost: price, steps: vec![]})).collect())
 	}
 }
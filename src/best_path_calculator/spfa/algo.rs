@@ -0,0 +1,83 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::{BTreeMap, VecDeque}, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, VecDeque};
+pub(crate) use super::super::algo_types::{Edge, Path, PathCalculationError, adjacency_list, predecessor_paths, vertex_count};
+#[cfg(test)]
+pub(crate) use super::super::algo_types::Pair;
+use super::super::bellman_ford::algo::bellman_ford;
+
+/// Shortest Path Faster Algorithm, ie. queue-based Bellman-Ford, single-source shortest paths. O(VE) worst case, though
+/// typically close to O(E), as only the edges out of vertices improved in the previous round are relaxed again.
+///
+/// Same output as `bellman_ford`: `target_vertex → Path` for every vertex reachable from `source`, the source itself mapping
+/// to an empty path with zero cost.
+///
+/// Without a negative cycle, each vertex is queued at most once per round, and there are at most V−1 rounds. A vertex
+/// relaxed into the queue V times thus sits past a negative cycle, which is rebuilt from its predecessors. Should these not
+/// close a cycle yet, falls back to `bellman_ford`.
+pub(crate) fn spfa(edges: &[Edge], source: usize) -> Result<BTreeMap<usize, Path>, PathCalculationError> {
+    let n = vertex_count(edges);
+    if source >= n {
+        return Ok(BTreeMap::new());
+    }
+    let adjacency = adjacency_list(edges, n);
+
+    // dist[v]      = best known cost source→v
+    // prev_edge[v] = incoming edge on the best known path to v
+    // queued[v]    = number of times v was relaxed into the queue
+    let mut dist      = vec![f64::INFINITY; n];
+    let mut prev_edge: Vec<Option<Edge>> = vec![None; n];
+    let mut queued    = vec![0_usize; n];
+    let mut in_queue  = vec![false; n];
+    let mut queue     = VecDeque::from([source]);
+    dist[source] = 0.0;
+    in_queue[source] = true;
+
+    while let Some(vertex) = queue.pop_front() {
+        in_queue[vertex] = false;
+        for &e in &adjacency[vertex] {
+            let new_d = dist[vertex] + e.cost;
+            if new_d < dist[e.pair.target] {
+                dist[e.pair.target] = new_d;
+                prev_edge[e.pair.target] = Some(e);
+                if !in_queue[e.pair.target] {
+                    queued[e.pair.target] += 1;
+                    if queued[e.pair.target] >= n {
+                        return match predecessor_cycle(&prev_edge, e.pair.target) {
+                            Some(cycle) => Err(PathCalculationError::NegativeCyclesError(cycle)),
+                            None => bellman_ford(edges, source),
+                        };
+                    }
+                    in_queue[e.pair.target] = true;
+                    queue.push_back(e.pair.target);
+                }
+            }
+        }
+    }
+
+    Ok(predecessor_paths(source, &dist, &prev_edge))
+}
+
+/// Cycle among the predecessors of `from`, if any, and negative. Any such cycle is negative in exact arithmetic, as each
+/// of its edges was relaxed after the one before it.
+fn predecessor_cycle(prev_edge: &[Option<Edge>], from: usize) -> Option<Path> {
+    let mut seen = vec![false; prev_edge.len()];
+    let mut on_cycle = from;
+    while !seen[on_cycle] {
+        seen[on_cycle] = true;
+        on_cycle = prev_edge[on_cycle]?.pair.source;
+    }
+
+    let mut edges = vec![];
+    let mut cur = on_cycle;
+    while let Some(e) = prev_edge[cur] {
+        edges.push(e);
+        cur = e.pair.source;
+        if cur == on_cycle { break; }
+    }
+    edges.reverse();
+
+    let total_cost = edges.iter().map(|e| e.cost).sum::<f64>();
+    (total_cost < 0.0).then_some(Path { total_cost, edges })
+}
@@ -0,0 +1,69 @@
+use super::algo::*;
+use crate::best_path_calculator::bellman_ford::algo::bellman_ford;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Graph of `n` vertices, each with edges to its successor and two pseudo-randomly picked others, costs of mixed sign
+/// without a negative cycle, by potentials: cost(i, j) = base + p(i) − p(j) with base ≥ 0.
+fn potential_graph(n: usize) -> Vec<Edge> {
+    let potential = |i: usize| ((i * 37) % 11) as f64;
+    (0..n).flat_map(|i| [(i + 1) % n, (i * 7 + 3) % n, (i * 13 + 5) % n].map(|j| (i, j)))
+        .filter(|&(i, j)| i != j)
+        .map(|(i, j)| Edge { pair: Pair { source: i, target: j }, provider: 0, cost: ((i * 5 + j * 3) % 4) as f64 + potential(i) - potential(j) })
+        .collect()
+}
+
+/// Simple chain 0→1→2 with a shortcut, as per Bellman-Ford.
+#[test]
+fn test_simple_chain() {
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost: 3.0 },
+        Edge { pair: Pair { source: 1, target: 2 }, provider: 0, cost: 2.0 },
+        Edge { pair: Pair { source: 0, target: 2 }, provider: 0, cost: 6.0 },
+    ];
+    let res = spfa(&edges, 0).unwrap();
+    assert_eq!(vec![0, 1, 2], res.keys().copied().collect::<Vec<_>>());
+    assert_eq!(5.0, res[&2].total_cost);
+    assert_eq!(vec![edges[0], edges[1]], res[&2].edges);
+    assert!(spfa(&edges, 2).unwrap().keys().eq([2].iter()));
+    assert!(spfa(&edges, 9).unwrap().is_empty());
+}
+
+/// Costs of mixed sign, from every source, must be those of Bellman-Ford.
+#[test]
+fn test_parity_with_bellman_ford() {
+    let n = 50;
+    let edges = potential_graph(n);
+    assert!(edges.iter().any(|e| e.cost < 0.0));
+    for source in 0..n {
+        let expected = bellman_ford(&edges, source).unwrap();
+        let res = spfa(&edges, source).unwrap();
+        assert_eq!(expected.keys().collect::<Vec<_>>(), res.keys().collect::<Vec<_>>());
+        for (target, path) in res {
+            assert!((expected[&target].total_cost - path.total_cost).abs() < 1e-9);
+            assert!((path.edges.iter().map(|e| e.cost).sum::<f64>() - path.total_cost).abs() < 1e-9);
+        }
+    }
+}
+
+/// A negative cycle reachable from the source is returned as a closed walk of negative cost, one that is not reachable
+/// is ignored.
+#[test]
+fn test_negative_cycle() {
+    let n = 50;
+    let mut edges = potential_graph(n);
+    edges.push(Edge { pair: Pair { source: 40, target: 3 }, provider: 1, cost: -50.0 });
+    let PathCalculationError::NegativeCyclesError(cycle) = spfa(&edges, 0).unwrap_err();
+    assert!(cycle.total_cost < 0.0);
+    assert!(cycle.edges.iter().zip(cycle.edges.iter().cycle().skip(1)).all(|(e, next)| e.pair.target == next.pair.source));
+
+    let edges = vec![
+        Edge { pair: Pair { source: 0, target: 1 }, provider: 0, cost:  1.0 },
+        Edge { pair: Pair { source: 2, target: 3 }, provider: 0, cost:  1.0 },
+        Edge { pair: Pair { source: 3, target: 2 }, provider: 0, cost: -2.0 },
+    ];
+    assert_eq!(2, spfa(&edges, 0).unwrap().len());
+    let PathCalculationError::NegativeCyclesError(cycle) = spfa(&edges, 2).unwrap_err();
+    assert_eq!(-1.0, cycle.total_cost);
+    assert_eq!(2, cycle.edges.len());
+}
@@ -0,0 +1,59 @@
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+use super::algo;
+use super::super::IndexedGraph;
+use super::super::algo_types::TransformedGraph;
+use super::super::bellman_ford::calculator::BellmanFordCalculator;
+use crate::types::*;
+use crate::*;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+pub use super::super::SCALE;
+
+pub struct SpfaCalculator {}
+
+impl<C: Currency, A: Amount, P: Provider> SingleSourceBestPathCalculator<C, A, P> for SpfaCalculator {
+
+    /// Calculates best paths from a single source currency using the queue-based Shortest Path Faster Algorithm, as per
+    /// `options`. Same result as `BellmanFordCalculator`, typically in fewer relaxations on sparse graphs.
+    ///
+    /// A negative cycle is detected by a currency being relaxed into the queue as many times as there are currencies, and
+    /// reported as per Bellman-Ford. Hop limited paths use Bellman-Ford.
+    ///
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::spfa::calculator::SpfaCalculator;
+    ///
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BNB", target: "USDT" }, provider: "CRYPTO_COMPARE" }, 364_190_000_000_000_u128),
+    ///     (ProviderPair { pair: Pair { source: "USDT", target: "ETH" }, provider: "COINGECKO" }, 2_745_000_000_u128),
+    /// ];
+    /// let res = SpfaCalculator::calc_best_paths_from(&"BNB", in_graph).unwrap();
+    /// assert_eq!(999_701_550_000_u128, res.get(&Pair { source: "BNB", target: "ETH" }).unwrap().total_cost);
+    /// ```
+    fn calc_best_paths_from_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        if options.max_hops.is_some() {
+            return BellmanFordCalculator::calc_best_paths_from_with(options, source, pairs_and_prices);
+        }
+        if pairs_and_prices.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let graph = IndexedGraph::new(options, pairs_and_prices)?;
+
+        let source_idx = match graph.currency_indexer.try_by_val(source) {
+            Some(idx) => idx,
+            None => return Ok(BTreeMap::new()),
+        };
+
+        let transformed = TransformedGraph::new(&graph.edges, options.accumulation, options.objective);
+        let res = transformed.run(|edges| algo::spfa(edges, source_idx))
+            .map_err(|err| graph.to_calculator_error(err))?;
+        graph.to_price_path_graph_from(source_idx, res)
+    }
+}
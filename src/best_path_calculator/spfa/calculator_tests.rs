@@ -0,0 +1,43 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use crate::types::*;
+use crate::{CalculatorOptions, SingleSourceBestPathCalculator};
+use super::calculator::{SpfaCalculator, SCALE};
+use crate::best_path_calculator::bellman_ford::calculator::BellmanFordCalculator;
+use crate::best_path_calculator::fixtures::{quote, real_life_graph};
+
+/// SPFA from every source, and with every option, must return the same paths as Bellman-Ford.
+#[test]
+fn test_parity_with_bellman_ford() {
+    let graph = real_life_graph();
+    let options = [
+        CalculatorOptions::default(),
+        CalculatorOptions { accumulation: Accumulation::Sum, objective: Objective::Minimise, ..Default::default() },
+        CalculatorOptions { max_hops: Some(2), ..Default::default() },
+    ];
+    for options in &options {
+        for source in ["BNB", "BTC", "DOT", "ETH", "USDT", "XYZ"] {
+            let source = source.to_owned();
+            assert_eq!(BellmanFordCalculator::calc_best_paths_from_with(options, &source, &graph).unwrap(),
+                       SpfaCalculator::calc_best_paths_from_with(options, &source, &graph).unwrap());
+        }
+    }
+}
+
+/// Arbitrage reachable from the source is reported as a cycle, with the profit ratio as total_cost.
+#[test]
+fn test_negative_cycle_reported() {
+    let mut graph = real_life_graph();
+    graph.push(quote("USDT", "DOT", "Q", 0.06)); // 17.43 * 0.06 = 1.0458
+    match SpfaCalculator::calc_best_paths_from(&"BNB".to_owned(), &graph) {
+        Err(CalculatorError::NegativeCyclesError(cycle)) => {
+            assert_eq!(cycle.steps.len(), 2);
+            assert_eq!(cycle.steps[0].pair.source, cycle.steps[1].pair.target);
+            assert_eq!(cycle.steps[1].pair.source, cycle.steps[0].pair.target);
+            assert!(cycle.steps.iter().any(|s| s.provider == "Q"));
+            assert!((cycle.total_cost as f64 / SCALE - 1.0458).abs() < 1e-9);
+        }
+        other => panic!("expected negative cycle, got {other:?}"),
+    }
+}
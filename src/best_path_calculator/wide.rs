@@ -0,0 +1,129 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use crate::types::{Accumulation, Rounding};
+
+/// Unsigned integer of unbounded width, as little endian 64 bit limbs, for exact products of amounts that overflow u128.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Wide(Vec<u64>);
+
+impl Wide {
+    pub(crate) fn from_u128(x: u128) -> Self {
+        let mut wide = Self(vec![x as u64, (x >> 64) as u64]);
+        wide.trim();
+        wide
+    }
+
+    /// Adds `x` in place.
+    pub(crate) fn add(&mut self, x: &Wide) {
+        self.0.resize(self.0.len().max(x.0.len()) + 1, 0);
+        let mut carry = 0_u128;
+        for (i, limb) in self.0.iter_mut().enumerate() {
+            let cur = *limb as u128 + x.0.get(i).copied().unwrap_or(0) as u128 + carry;
+            *limb = cur as u64;
+            carry = cur >> 64;
+        }
+        self.trim();
+    }
+
+    /// Multiplies in place by `x`.
+    pub(crate) fn mul_u128(&mut self, x: u128) {
+        let mut product = vec![0_u64; self.0.len() + 2];
+        for (shift, factor) in [(0, x as u64), (1, (x >> 64) as u64)] {
+            // limb * factor + product limb + carry < 2^128
+            let mut carry = 0_u128;
+            for (i, &limb) in self.0.iter().enumerate() {
+                let cur = product[i + shift] as u128 + limb as u128 * factor as u128 + carry;
+                product[i + shift] = cur as u64;
+                carry = cur >> 64;
+            }
+            let mut i = self.0.len() + shift;
+            while carry > 0 {
+                let cur = product[i] as u128 + carry;
+                product[i] = cur as u64;
+                carry = cur >> 64;
+                i += 1;
+            }
+        }
+        self.0 = product;
+        self.trim();
+    }
+
+    /// Divides in place by `d` > 0, rounding down, returning the remainder. Limb by limb for `d` of up to 64 bits, otherwise
+    /// bit by bit.
+    pub(crate) fn div_u128(&mut self, d: u128) -> u128 {
+        let mut rem = 0_u128;
+        if d <= u64::MAX as u128 {
+            for limb in self.0.iter_mut().rev() {
+                let cur = (rem << 64) | *limb as u128;
+                *limb = (cur / d) as u64;
+                rem = cur % d;
+            }
+        } else {
+            for limb in self.0.iter_mut().rev() {
+                let mut quotient = 0_u64;
+                for bit in (0..64).rev() {
+                    // the remainder may take 129 bits before subtracting, the top one being `overflow`
+                    let overflow = rem >> 127 == 1;
+                    rem = (rem << 1) | ((*limb >> bit) & 1) as u128;
+                    if overflow || rem >= d {
+                        rem = rem.wrapping_sub(d);
+                        quotient |= 1 << bit;
+                    }
+                }
+                *limb = quotient;
+            }
+        }
+        self.trim();
+        rem
+    }
+
+    /// The value, if it fits u128.
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        match self.0.as_slice() {
+            [] => Some(0),
+            [lo] => Some(*lo as u128),
+            [lo, hi] => Some(*lo as u128 | (*hi as u128) << 64),
+            _ => None,
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+}
+
+/// Exact sum or product of `amounts`, in scale of `scale`, None if it overflows u128.
+///
+/// Products are multiplied out over unbounded width, then divided by `scale` once less than there are amounts, rounding
+/// just once, as per `rounding`. The empty product is `scale`, ie. 1.
+pub(crate) fn accumulate(amounts: impl Iterator<Item = u128>, scale: u128, accumulation: Accumulation, rounding: Rounding) -> Option<u128> {
+    let mut amounts = amounts.peekable();
+    match accumulation {
+        Accumulation::Sum => amounts.try_fold(0_u128, |acc, amount| acc.checked_add(amount)),
+        Accumulation::Product if amounts.peek().is_none() => Some(scale),
+        Accumulation::Product => {
+            let mut product = Wide::from_u128(amounts.next().unwrap());
+            let mut divisor = Wide::from_u128(1);
+            let mut divisions = 0;
+            for amount in amounts {
+                product.mul_u128(amount);
+                divisor.mul_u128(scale);
+                divisions += 1;
+            }
+            if rounding == Rounding::HalfUp {
+                // ⌊(2·product + divisor) / (2·divisor)⌋
+                product.mul_u128(2);
+                product.add(&divisor);
+                product.div_u128(2);
+            }
+            let mut exact = true;
+            for _ in 0..divisions {
+                exact &= product.div_u128(scale) == 0;
+            }
+            let rounded = product.to_u128()?;
+            if rounding == Rounding::Up && !exact { rounded.checked_add(1) } else { Some(rounded) }
+        }
+    }
+}
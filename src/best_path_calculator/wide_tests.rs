@@ -0,0 +1,74 @@
+use super::wide::{Wide, accumulate};
+use crate::types::{Accumulation, Rounding};
+
+const SCALE: u128 = 1_000_000_000_000;
+
+#[test]
+fn test_mul_div() {
+    let mut x = Wide::from_u128(u128::MAX);
+    x.mul_u128(u128::MAX);
+    assert_eq!(None, x.to_u128());
+    assert_eq!(0, x.div_u128(u128::MAX));
+    assert_eq!(Some(u128::MAX), x.to_u128());
+
+    let mut x = Wide::from_u128(1_000_000_000_000);
+    for _ in 0..4 {
+        x.mul_u128(3_000_000_000_000);
+    }
+    assert_eq!(None, x.to_u128());
+    for _ in 0..5 {
+        assert_eq!(0, x.div_u128(1_000_000_000_000));
+    }
+    assert_eq!(Some(81), x.to_u128());
+    assert_eq!(1, x.div_u128(2));
+    assert_eq!(Some(40), x.to_u128());
+}
+
+#[test]
+fn test_div_wide_divisor() {
+    let d = (1_u128 << 127) + 12_345;
+    let mut x = Wide::from_u128(d);
+    x.mul_u128(1 << 100);
+    x.mul_u128(7);
+    assert_eq!(0, x.div_u128(d));
+    assert_eq!(Some(7 << 100), x.to_u128());
+
+    let mut x = Wide::from_u128(d - 1);
+    assert_eq!(d - 1, x.div_u128(d));
+    assert_eq!(Some(0), x.to_u128());
+}
+
+/// Products are exact before rounding once.
+#[test]
+fn test_accumulate() {
+    let product = |amounts: &[u128], rounding| accumulate(amounts.iter().copied(), SCALE, Accumulation::Product, rounding);
+    assert_eq!(Some(SCALE), product(&[], Rounding::Down));
+    assert_eq!(Some(0), accumulate([].into_iter(), SCALE, Accumulation::Sum, Rounding::Down));
+    // 1.5 * 1.5 * 1.5 = 3.375, 0.333333333333 * 3 = 0.999999999999
+    assert_eq!(Some(3_375_000_000_000), product(&[1_500_000_000_000; 3], Rounding::Down));
+    assert_eq!(Some(999_999_999_999), product(&[333_333_333_333, 3 * SCALE], Rounding::Down));
+    // intermediate product beyond u128
+    assert_eq!(Some(u128::MAX / 2), product(&[u128::MAX / 2, SCALE, SCALE], Rounding::Down));
+    assert_eq!(None, product(&[u128::MAX, 2 * SCALE], Rounding::Down));
+    assert_eq!(None, accumulate([u128::MAX, 1].into_iter(), SCALE, Accumulation::Sum, Rounding::Down));
+}
+
+#[test]
+fn test_accumulate_rounding() {
+    let product = |amounts: &[u128], rounding| accumulate(amounts.iter().copied(), SCALE, Accumulation::Product, rounding);
+    // 0.000000000001^2
+    assert_eq!(Some(0), product(&[1, 1], Rounding::Down));
+    assert_eq!(Some(0), product(&[1, 1], Rounding::HalfUp));
+    assert_eq!(Some(1), product(&[1, 1], Rounding::Up));
+    // 0.0000000000015 * 1 rounds half up, 0.0000000000014999... down
+    assert_eq!(Some(1), product(&[1_500_000_000_000, 1], Rounding::Down));
+    assert_eq!(Some(2), product(&[1_500_000_000_000, 1], Rounding::HalfUp));
+    assert_eq!(Some(1), product(&[1_499_999_999_999, 1], Rounding::HalfUp));
+    assert_eq!(Some(2), product(&[1_499_999_999_999, 1], Rounding::Up));
+    // exact products are not rounded, over several divisions too
+    for rounding in [Rounding::Down, Rounding::HalfUp, Rounding::Up] {
+        assert_eq!(Some(3_375_000_000_000), product(&[1_500_000_000_000; 3], rounding));
+        assert_eq!(Some(7), accumulate([3, 4].into_iter(), SCALE, Accumulation::Sum, rounding));
+    }
+    assert_eq!(None, product(&[u128::MAX, SCALE + 1], Rounding::Up));
+}
@@ -1,10 +1,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+// tests draw graphs in doc comments, which newer clippy reads as overindented list items, and keep their original idioms
+#![cfg_attr(test, allow(unknown_lints, clippy::doc_overindented_list_items, clippy::useless_vec, clippy::iter_next_slice))]
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
 #[cfg(not(feature = "std"))]
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, vec::Vec};
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 
@@ -13,15 +15,135 @@ mod types;
 use types::*;
 
 pub type PricePathGraph<C, A, P> = BTreeMap<Pair<C>, PricePath<C, A, P>>;
+pub type PricePaths<C, A, P> = Vec<PricePath<C, A, P>>;
+pub type WidestPathGraph<C, A, P> = BTreeMap<Pair<C>, WidestPath<C, A, P>>;
+/// Decimals per currency, eg. 8 for BTC in sats, 18 for ETH in wei, for amounts in base units.
+pub type CurrencyDecimals<C> = BTreeMap<C, u8>;
 
 /// Interface for calculating best paths between all currency pairs. O(V³).
 pub trait AllPairsBestPathCalculator<C: Currency, A: Amount, P: Provider> {
-    fn calc_best_paths(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError>;
+    fn calc_best_paths(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_best_paths_with(&CalculatorOptions::default(), pairs_and_prices)
+    }
+
+    fn calc_best_paths_with(
+        options: &CalculatorOptions<C>,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating widest paths between all currency pairs, maximising the smallest capacity along the path. O(V³).
+/// Quotes are `(provider pair, rate, capacity)`. Self references, of unbounded capacity, are left out.
+pub trait AllPairsWidestPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_widest_paths(quotes: &[(ProviderPair<C, P>, A, A)]) -> Result<WidestPathGraph<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating widest paths from a single source currency, as per `AllPairsWidestPathCalculator`. O(VE).
+pub trait SingleSourceWidestPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_widest_paths_from(source: &C, quotes: &[(ProviderPair<C, P>, A, A)]) -> Result<WidestPathGraph<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for listing arbitrage opportunities: every elementary cycle whose product of rates exceeds 1, most profitable first.
+/// Each cycle is a `PricePath` starting and ending in the same currency, with `total_cost` being its profit ratio.
+pub trait ArbitrageDetector<C: Currency, A: Amount, P: Provider> {
+    fn find_arbitrage_cycles(pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>>;
 }
 
 /// Interface for calculating best paths from a single source currency. O(VE).
 pub trait SingleSourceBestPathCalculator<C: Currency, A: Amount, P: Provider> {
-    fn calc_best_paths_from(source: &C, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError>;
+    fn calc_best_paths_from(source: &C, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_best_paths_from_with(&CalculatorOptions::default(), source, pairs_and_prices)
+    }
+
+    fn calc_best_paths_from_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating best paths from every currency into a single target currency, eg. for valuing all holdings in
+/// a numeraire.
+pub trait SingleTargetBestPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_best_paths_to(target: &C, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_best_paths_to_with(&CalculatorOptions::default(), target, pairs_and_prices)
+    }
+
+    fn calc_best_paths_to_with(
+        options: &CalculatorOptions<C>,
+        target: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating best paths from every currency of a source set to every currency of a target set, eg. from
+/// treasury to settlement currencies, leaving out any other pair.
+pub trait MultiPairBestPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_best_paths_between(sources: &[C], targets: &[C], pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_best_paths_between_with(&CalculatorOptions::default(), sources, targets, pairs_and_prices)
+    }
+
+    fn calc_best_paths_between_with(
+        options: &CalculatorOptions<C>,
+        sources: &[C],
+        targets: &[C],
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePathGraph<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating the best path between a single pair of currencies, None if the target is unreachable.
+/// Avoids calculating paths to all targets, either by stopping as soon as that path is settled, or by pruning to the
+/// quotes between the pair.
+#[allow(clippy::type_complexity)]
+pub trait SinglePairBestPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_best_path(source: &C, target: &C, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<Option<PricePath<C, A, P>>, CalculatorError<C, A, P>> {
+        Self::calc_best_path_with(&CalculatorOptions::default(), source, target, pairs_and_prices)
+    }
+
+    fn calc_best_path_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<Option<PricePath<C, A, P>>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating the `k` best loopless paths between a pair of currencies, best first, eg. as fallback routes.
+pub trait KBestPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_k_best_paths(source: &C, target: &C, k: usize, pairs_and_prices: &[(ProviderPair<C, P>, A)]) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_k_best_paths_with(&CalculatorOptions::default(), source, target, k, pairs_and_prices)
+    }
+
+    fn calc_k_best_paths_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        k: usize,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>>;
+}
+
+/// Interface for calculating up to `k` redundant routes between a pair of currencies, sharing no intermediate currency or
+/// no provider as per `Disjointness`, best first. Then no single outage breaks every route.
+pub trait DisjointPathCalculator<C: Currency, A: Amount, P: Provider> {
+    fn calc_disjoint_paths(
+        source: &C,
+        target: &C,
+        k: usize,
+        disjointness: Disjointness,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>> {
+        Self::calc_disjoint_paths_with(&CalculatorOptions::default(), source, target, k, disjointness, pairs_and_prices)
+    }
+
+    fn calc_disjoint_paths_with(
+        options: &CalculatorOptions<C>,
+        source: &C,
+        target: &C,
+        k: usize,
+        disjointness: Disjointness,
+        pairs_and_prices: &[(ProviderPair<C, P>, A)],
+    ) -> Result<PricePaths<C, A, P>, CalculatorError<C, A, P>>;
 }
 
 pub mod prelude {
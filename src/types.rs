@@ -4,6 +4,7 @@ use alloc::vec::Vec;
 use codec::{Decode, Encode};
 #[cfg(feature = "scale")]
 use scale_info::TypeInfo;
+use crate::CurrencyDecimals;
 
 /// Currency representation, eg. "BTC".
 pub trait Currency: Ord + Clone {}
@@ -50,8 +51,117 @@ pub struct PathStep<C: Currency, A: Amount, P: Provider> {
     pub cost: A,
 }
 
-#[derive(Debug)]
-pub enum CalculatorError {
-    NegativeCyclesError,
+/// Path maximising the smallest capacity along it, eg. liquidity available for a trade. `path.total_cost` is the product of
+/// rates, as per `PricePath`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub struct WidestPath<C: Currency, A: Amount, P: Provider> {
+    pub capacity: A,
+    pub path: PricePath<C, A, P>,
+}
+
+/// How costs accumulate along a path.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub enum Accumulation {
+    /// Costs add up, eg. latency or fees.
+    Sum,
+    /// Costs multiply, eg. exchange rates.
+    #[default]
+    Product,
+}
+
+/// Whether the best path is the one with the lowest or the highest accumulated cost.
+/// Minimising a product suits multiplicative cost factors, eg. slippage, and treats zero rates as missing quotes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub enum Objective {
+    Minimise,
+    #[default]
+    Maximise,
+}
+
+/// How an exact total is rounded into the scale of amounts, eg. a product of rates.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub enum Rounding {
+    /// Towards zero, never overstating a total.
+    #[default]
+    Down,
+    /// To the nearest, halves up.
+    HalfUp,
+    /// Away from zero, never understating a total.
+    Up,
+}
+
+/// What redundant routes must not have in common.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, TypeInfo))]
+pub enum Disjointness {
+    /// No intermediate currency, nor a currency pair, is shared.
+    Currency,
+    /// No provider is shared.
+    Provider,
+}
+
+/// Settings accepted by the calculators. Defaults to maximising the product of exchange rates, with no limit on path length,
+/// for amounts in scale of `SCALE`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CalculatorOptions<C> {
+    pub accumulation: Accumulation,
+    pub objective: Objective,
+    /// Maximum number of steps per path, eg. to cap the number of trades. Paths are then the best walks within the limit,
+    /// which may pass through a cycle rather than report it as `NegativeCyclesError`.
+    pub max_hops: Option<usize>,
+    /// Rounding of `total_cost`, recomputed exactly from the amounts of its steps.
+    pub rounding: Rounding,
+    /// Fixed-point scale of amounts, ie. the amount of a rate of 1, eg. 10^18 for EVM sourced quotes. Self references cost
+    /// this much when multiplying.
+    pub scale: u128,
+    /// Decimals of every currency quoted, for amounts being ratios of base units, eg. wei per sat, in scale of `scale`.
+    /// Products are then normalised into rates by the decimals of their pair, and `total_cost` is in base units of the
+    /// target, per unit of the source. Fails with `ConversionError` for a currency missing, or where 10^decimals overflows
+    /// u128. None if empty, the default.
+    /// ```rust
+    /// # use best_path::prelude::*;
+    /// # use best_path::prelude::floyd_warshall::calculator::FloydWarshallCalculator;
+    ///
+    /// let options = CalculatorOptions { decimals: CurrencyDecimals::from([("BTC", 8), ("ETH", 18)]), ..Default::default() };
+    /// // 15.09 ETH per BTC, ie. 150_900_000_000 wei per sat
+    /// let in_graph = &[
+    ///     (ProviderPair { pair: Pair { source: "BTC", target: "ETH" }, provider: "P" }, 150_900_000_000_000_000_000_000_u128),
+    /// ];
+    /// let res = FloydWarshallCalculator::calc_best_paths_with(&options, in_graph).unwrap();
+    /// assert_eq!(15_090_000_000_000_000_000_u128, res[&Pair { source: "BTC", target: "ETH" }].total_cost);
+    /// assert_eq!(100_000_000_u128, res[&Pair { source: "BTC", target: "BTC" }].total_cost);
+    /// ```
+    pub decimals: CurrencyDecimals<C>,
+}
+
+impl<C> Default for CalculatorOptions<C> {
+    fn default() -> Self {
+        Self {
+            accumulation: Accumulation::default(),
+            objective: Objective::default(),
+            max_hops: None,
+            rounding: Rounding::default(),
+            scale: crate::best_path_calculator::SCALE as u128,
+            decimals: CurrencyDecimals::new(),
+        }
+    }
+}
+
+/// Calculation failure.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum CalculatorError<C: Currency, A: Amount, P: Provider> {
+    /// Graph contains a cycle that improves on every pass, eg. an arbitrage cycle (product > 1) when maximising the product of
+    /// exchange rates, or a cycle with product < 1 when minimising, so best paths are unbounded. Carries one such cycle, starting and ending in the same currency, with `total_cost` being
+    /// its accumulated cost (for arbitrage, the profit ratio) in the same scale as any other path cost.
+    NegativeCyclesError(PricePath<C, A, P>),
     ConversionError,
+    /// A result does not fit `Amount`, eg. u64, such as a large product of rates. Carries the pair of the path at fault, or of
+    /// its step.
+    OverflowError(Pair<C>),
+    /// An option the calculation cannot honour, eg. `max_hops` for a lazy or incremental calculation.
+    UnsupportedOptionError,
 }